@@ -3,12 +3,11 @@ use frei0r_rs2::*;
 pub struct ShiftPlugin {
     xshift: f64,
     yshift: f64,
-    width: usize,
-    height: usize,
 }
 
 impl Plugin for ShiftPlugin {
     type Kind = KindFilter;
+    type Pixel = Rgba8;
 
     const PARAMS: &'static [ParamInfo<Self>] = &[
         ParamInfo::new_double(
@@ -26,35 +25,35 @@ impl Plugin for ShiftPlugin {
     ];
 
     fn info() -> PluginInfo {
-        PluginInfo {
+        plugin_info!(
             name: c"frei0r-rs2 shift",
             author: c"none",
-            color_model: ColorModel::RGBA8888,
-            major_version: 1,
-            minor_version: 0,
             explanation: Some(c"Filter plugin used for the testing of frei0r-rs2"),
-        }
+        )
     }
 
-    fn new(width: usize, height: usize) -> Self {
+    fn new(_width: usize, _height: usize) -> Self {
         Self {
             xshift: 0.0,
             yshift: 0.0,
-            width,
-            height,
         }
     }
 }
 
-impl FilterPlugin for ShiftPlugin {
-    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
-        let xshift = (self.xshift * self.width as f64) as usize;
-        let yshift = (self.yshift * self.height as f64) as usize;
-        for dy in 0..self.height {
-            for dx in 0..self.width {
-                let sy = (dy + yshift) % self.height;
-                let sx = (dx + xshift) % self.width;
-                outframe[dy * self.width + dx] = inframe[sy * self.width + sx];
+impl FilterFramePlugin for ShiftPlugin {
+    fn update_filter_frame(
+        &mut self,
+        _time: f64,
+        inframe: Frame<'_, Rgba8>,
+        mut outframe: FrameMut<'_, Rgba8>,
+    ) {
+        let (width, height) = (inframe.width(), inframe.height());
+        let xshift = (self.xshift * width as f64) as usize;
+        let yshift = (self.yshift * height as f64) as usize;
+        for (dy, row) in outframe.rows_mut().enumerate() {
+            let source = inframe.row((dy + yshift) % height);
+            for (dx, pixel) in row.iter_mut().enumerate() {
+                *pixel = source[(dx + xshift) % width];
             }
         }
     }
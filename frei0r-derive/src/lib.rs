@@ -0,0 +1,154 @@
+//! Derive macros for [frei0r-rs2](https://docs.rs/frei0r-rs2).
+//!
+//! Use through the `derive` feature of `frei0r-rs2`, which re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use std::ffi::CString;
+use syn::{
+    Data, DeriveInput, Error, Fields, Ident, LitCStr, LitStr, Result, Type, ext::IdentExt,
+    parse_macro_input, spanned::Spanned,
+};
+
+/// Derive `frei0r_rs2::Frei0rParams`, generating the parameter list from fields annotated with
+/// `#[param(...)]`.
+///
+/// See the documentation of `frei0r_rs2::Frei0rParams` for the supported attributes and types.
+#[proc_macro_derive(Frei0rParams, attributes(param))]
+pub fn derive_frei0r_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "Frei0rParams can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            data.fields.span(),
+            "Frei0rParams requires a struct with named fields",
+        ));
+    };
+
+    let mut params = Vec::new();
+    for field in &fields.named {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("param"))
+        else {
+            continue;
+        };
+        let ident = field.ident.as_ref().expect("named field");
+        let mut name = None;
+        let mut explanation = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("explanation") {
+                explanation = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name` or `explanation`"))
+            }
+        })?;
+        let name = match name {
+            Some(name) => c_str(&name)?,
+            // A field `r#type` is reported as `type`.
+            None => LitCStr::new(
+                &CString::new(ident.unraw().to_string()).unwrap(),
+                ident.span(),
+            ),
+        };
+        let Some(explanation) = explanation else {
+            return Err(Error::new(
+                attr.span(),
+                "missing `explanation = \"...\"` in #[param]",
+            ));
+        };
+        let explanation = c_str(&explanation)?;
+        params.push(param(ident, &field.ty, &name, &explanation)?);
+    }
+
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::frei0r_rs2::Frei0rParams for #ty #ty_generics #where_clause {
+            const PARAMS: &'static [::frei0r_rs2::ParamInfo<Self>] = &[#(#params),*];
+        }
+    })
+}
+
+fn c_str(lit: &LitStr) -> Result<LitCStr> {
+    let value = CString::new(lit.value())
+        .map_err(|_| Error::new(lit.span(), "string must not contain a NUL byte"))?;
+    Ok(LitCStr::new(&value, lit.span()))
+}
+
+fn param(ident: &Ident, ty: &Type, name: &LitCStr, explanation: &LitCStr) -> Result<TokenStream2> {
+    let kind = match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    };
+    let span = ty.span();
+    let (constructor, get, set) = match kind.as_deref() {
+        Some("bool") => (
+            quote!(new_bool),
+            quote_spanned!(span=> plugin.#ident),
+            quote_spanned!(span=> plugin.#ident = value),
+        ),
+        Some("f64") => (
+            quote!(new_double),
+            quote_spanned!(span=> plugin.#ident),
+            quote_spanned!(span=> plugin.#ident = value),
+        ),
+        Some("Color") => (
+            quote!(new_color),
+            quote_spanned!(span=> plugin.#ident),
+            quote_spanned!(span=> plugin.#ident = *value),
+        ),
+        Some("Position") => (
+            quote!(new_position),
+            quote_spanned!(span=> plugin.#ident),
+            quote_spanned!(span=> plugin.#ident = *value),
+        ),
+        Some("String") => (
+            quote!(new_string_owned),
+            quote_spanned!(span=> plugin.#ident.as_str()),
+            quote_spanned!(span=> plugin.#ident = value),
+        ),
+        Some("CString") => (
+            quote!(new_string),
+            quote_spanned!(span=> plugin.#ident.as_c_str()),
+            quote_spanned!(span=> plugin.#ident = value.to_owned()),
+        ),
+        _ => {
+            return Err(Error::new(
+                ty.span(),
+                "unsupported parameter type, expected one of `bool`, `f64`, `Color`, `Position`, `String` or `CString`",
+            ));
+        }
+    };
+    // Types are matched by name, spanning the expansion to the field type reports a field of
+    // another type with the same name there.
+    Ok(quote_spanned! {span=>
+        ::frei0r_rs2::ParamInfo::#constructor(
+            #name,
+            #explanation,
+            |plugin| #get,
+            |plugin, value| #set,
+        )
+    })
+}
@@ -0,0 +1,160 @@
+use std::{
+    any::Any,
+    error::Error,
+    ffi::{CStr, c_int},
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::RwLock,
+};
+
+/// A problem detected at the frei0r C API boundary.
+///
+/// Diagnostics are passed to the hook installed with [set_diagnostic_hook].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Diagnostic<'a> {
+    /// Plugin code panicked inside the C API function `entry_point`.
+    ///
+    /// If the panic happened inside an instance, that instance is poisoned and is no longer
+    /// called into: parameter calls are ignored and updates clear the output frame.
+    Panic {
+        entry_point: &'static str,
+        message: &'a str,
+    },
+    /// The application passed a parameter index outside `0..num_params`, the call was ignored.
+    InvalidParamIndex {
+        entry_point: &'static str,
+        index: c_int,
+    },
+    /// The application passed a null pointer for `argument`, the call was ignored.
+    NullPointer {
+        entry_point: &'static str,
+        argument: &'static str,
+    },
+    /// `f0r_set_param_value` was called with a name that is not one of the choices of a choice
+    /// parameter, the value was ignored.
+    UnknownChoice { index: c_int, value: &'a CStr },
+    /// `f0r_set_param_value` was called with a name that is not one of the presets of the plugin,
+    /// the value was ignored.
+    UnknownPreset { value: &'a CStr },
+    /// The plugin failed to initialize its global state, `f0r_init` returned 0.
+    InitFailed {
+        error: &'a (dyn Error + Send + Sync),
+    },
+    /// The plugin refused to construct an instance, `f0r_construct` returned null.
+    ConstructFailed {
+        width: u32,
+        height: u32,
+        error: &'a (dyn Error + Send + Sync),
+    },
+    /// A preset sets a parameter that does not exist or has a different type, the value was
+    /// skipped.
+    InvalidPresetValue { preset: &'a CStr, param: &'a CStr },
+    /// The resource of a path parameter failed to load, the plugin sees no resource until a new
+    /// path is set.
+    LoadFailed {
+        path: &'a Path,
+        error: &'a (dyn Error + Send + Sync),
+    },
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Panic {
+                entry_point,
+                message,
+            } => write!(f, "frei0r plugin panicked in {entry_point}: {message}"),
+            Diagnostic::InvalidParamIndex { entry_point, index } => {
+                write!(
+                    f,
+                    "{entry_point} called with invalid parameter index {index}"
+                )
+            }
+            Diagnostic::NullPointer {
+                entry_point,
+                argument,
+            } => write!(f, "{entry_point} called with null {argument}"),
+            Diagnostic::UnknownChoice { index, value } => write!(
+                f,
+                "f0r_set_param_value called with unknown choice {value:?} for parameter {index}"
+            ),
+            Diagnostic::UnknownPreset { value } => {
+                write!(
+                    f,
+                    "f0r_set_param_value called with unknown preset {value:?}"
+                )
+            }
+            Diagnostic::InitFailed { error } => {
+                write!(f, "frei0r plugin failed to initialize: {error}")
+            }
+            Diagnostic::ConstructFailed {
+                width,
+                height,
+                error,
+            } => write!(
+                f,
+                "frei0r plugin failed to construct a {width}x{height} instance: {error}"
+            ),
+            Diagnostic::InvalidPresetValue { preset, param } => write!(
+                f,
+                "preset {preset:?} sets unknown parameter {param:?} or uses the wrong type"
+            ),
+            Diagnostic::LoadFailed { path, error } => {
+                write!(
+                    f,
+                    "frei0r plugin failed to load {}: {error}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+// The plugin runs inside an application that owns stderr, diagnostics are only reported once the
+// plugin opts in. Panic messages are still printed by the standard panic hook.
+fn default_hook(_diagnostic: &Diagnostic<'_>) {}
+
+static HOOK: RwLock<fn(&Diagnostic<'_>)> = RwLock::new(default_hook);
+
+/// Install the function called for every [Diagnostic]. By default diagnostics are ignored.
+///
+/// Panics can only be caught if the plugin is built with `panic = "unwind"` (the default).
+///
+/// ```
+/// frei0r_rs2::set_diagnostic_hook(|diagnostic| eprintln!("{diagnostic}"));
+/// ```
+pub fn set_diagnostic_hook(hook: fn(&Diagnostic<'_>)) {
+    *HOOK.write().unwrap_or_else(|err| err.into_inner()) = hook;
+}
+
+pub(crate) fn report(diagnostic: &Diagnostic<'_>) {
+    let hook = *HOOK.read().unwrap_or_else(|err| err.into_inner());
+    // A panicking hook must not unwind into the host either.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| hook(diagnostic)));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic payload"
+    }
+}
+
+/// Run `f`, catching and reporting a panic instead of letting it unwind across the C boundary.
+pub(crate) fn catch<R>(entry_point: &'static str, f: impl FnOnce() -> R) -> Option<R> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            report(&Diagnostic::Panic {
+                entry_point,
+                message: panic_message(payload.as_ref()),
+            });
+            None
+        }
+    }
+}
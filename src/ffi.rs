@@ -1,7 +1,15 @@
-use crate::param::{Color, ParamInfo, ParamKind, Position};
-use crate::{ColorModel, FilterPlugin, Mixer2Plugin, Mixer3Plugin, Plugin, SourcePlugin};
+use crate::diagnostic::{self, Diagnostic};
+use crate::global;
+use crate::param::{ChoiceEncoding, Color, ParamDefault, ParamInfo, ParamKind, ParamSet, Position};
+use crate::preset::Preset;
+use crate::smoothing::{Smoother, Smoothing};
+use crate::{
+    BoxError, ColorModel, DimensionError, FilterFramePlugin, Frame, FrameMut, Mixer2FramePlugin,
+    Mixer3FramePlugin, ParamValue, Pixel, Plugin, SourceFramePlugin,
+};
 pub use frei0r_sys2::*;
-use std::ffi::{CStr, c_int, c_uint};
+use std::borrow::Cow;
+use std::ffi::{CStr, CString, c_int, c_uint};
 
 mod private {
     pub trait Sealed {}
@@ -12,6 +20,8 @@ mod private {
 pub trait PluginKind: private::Sealed {
     #[doc(hidden)]
     const PLUGIN_TYPE: i32;
+    #[doc(hidden)]
+    const INPUT_COUNT: usize;
 }
 
 /// Marker type representing a Source plugin.
@@ -21,6 +31,8 @@ impl private::Sealed for KindSource {}
 impl PluginKind for KindSource {
     #[doc(hidden)]
     const PLUGIN_TYPE: i32 = F0R_PLUGIN_TYPE_SOURCE as i32;
+    #[doc(hidden)]
+    const INPUT_COUNT: usize = 0;
 }
 
 /// Marker type representing a Filter plugin.
@@ -30,6 +42,8 @@ impl private::Sealed for KindFilter {}
 impl PluginKind for KindFilter {
     #[doc(hidden)]
     const PLUGIN_TYPE: i32 = F0R_PLUGIN_TYPE_FILTER as i32;
+    #[doc(hidden)]
+    const INPUT_COUNT: usize = 1;
 }
 
 /// Marker type representing a Mixer2 plugin.
@@ -39,6 +53,8 @@ impl private::Sealed for KindMixer2 {}
 impl PluginKind for KindMixer2 {
     #[doc(hidden)]
     const PLUGIN_TYPE: i32 = F0R_PLUGIN_TYPE_MIXER2 as i32;
+    #[doc(hidden)]
+    const INPUT_COUNT: usize = 2;
 }
 
 /// Marker type representing a Mixer3 plugin.
@@ -48,6 +64,8 @@ impl private::Sealed for KindMixer3 {}
 impl PluginKind for KindMixer3 {
     #[doc(hidden)]
     const PLUGIN_TYPE: i32 = F0R_PLUGIN_TYPE_MIXER3 as i32;
+    #[doc(hidden)]
+    const INPUT_COUNT: usize = 3;
 }
 
 // Bridges between type-level plugin kinds and runtime update behavior.
@@ -58,115 +76,333 @@ impl PluginKind for KindMixer3 {
 // this trait dispatches to the appropriate plugin-specific method based on
 // the PluginKind type parameter `K`.
 #[doc(hidden)]
-pub trait PluginKindUpdate<K: PluginKind> {
+pub trait PluginKindUpdate<K: PluginKind>: Plugin {
     fn update(
         &mut self,
-        frame_length: usize,
         time: f64,
-        inframe1: *const u32,
-        inframe2: *const u32,
-        inframe3: *const u32,
-        outframe: &mut [u32],
+        inframes: [Option<Frame<'_, Self::Pixel>>; 3],
+        outframe: FrameMut<'_, Self::Pixel>,
     );
 }
 
 impl<T> PluginKindUpdate<KindSource> for T
 where
-    T: SourcePlugin,
+    T: SourceFramePlugin,
 {
     fn update(
         &mut self,
-        _frame_length: usize,
         time: f64,
-        _inframe1: *const u32,
-        _inframe2: *const u32,
-        _inframe3: *const u32,
-        outframe: &mut [u32],
+        _inframes: [Option<Frame<'_, T::Pixel>>; 3],
+        outframe: FrameMut<'_, T::Pixel>,
     ) {
-        self.update_source(time, outframe);
+        self.update_source_frame(time, outframe);
     }
 }
 
 impl<T> PluginKindUpdate<KindFilter> for T
 where
-    T: FilterPlugin,
+    T: FilterFramePlugin,
 {
     fn update(
         &mut self,
-        frame_length: usize,
         time: f64,
-        inframe1: *const u32,
-        _inframe2: *const u32,
-        _inframe3: *const u32,
-        outframe: &mut [u32],
+        inframes: [Option<Frame<'_, T::Pixel>>; 3],
+        outframe: FrameMut<'_, T::Pixel>,
     ) {
-        self.update_filter(time, frame_to_slice(&inframe1, frame_length), outframe);
+        let [inframe1, _, _] = inframes;
+        self.update_filter_frame(time, input(inframe1), outframe);
     }
 }
 
 impl<T> PluginKindUpdate<KindMixer2> for T
 where
-    T: Mixer2Plugin,
+    T: Mixer2FramePlugin,
 {
     fn update(
         &mut self,
-        frame_length: usize,
         time: f64,
-        inframe1: *const u32,
-        inframe2: *const u32,
-        _inframe3: *const u32,
-        outframe: &mut [u32],
+        inframes: [Option<Frame<'_, T::Pixel>>; 3],
+        outframe: FrameMut<'_, T::Pixel>,
     ) {
-        self.update_mixer2(
-            time,
-            frame_to_slice(&inframe1, frame_length),
-            frame_to_slice(&inframe2, frame_length),
-            outframe,
-        );
+        let [inframe1, inframe2, _] = inframes;
+        self.update_mixer2_frame(time, input(inframe1), input(inframe2), outframe);
     }
 }
 
 impl<T> PluginKindUpdate<KindMixer3> for T
 where
-    T: Mixer3Plugin,
+    T: Mixer3FramePlugin,
 {
     fn update(
         &mut self,
-        frame_length: usize,
         time: f64,
-        inframe1: *const u32,
-        inframe2: *const u32,
-        inframe3: *const u32,
-        outframe: &mut [u32],
+        inframes: [Option<Frame<'_, T::Pixel>>; 3],
+        outframe: FrameMut<'_, T::Pixel>,
     ) {
-        self.update_mixer3(
+        let [inframe1, inframe2, inframe3] = inframes;
+        self.update_mixer3_frame(
             time,
-            frame_to_slice(&inframe1, frame_length),
-            frame_to_slice(&inframe2, frame_length),
-            frame_to_slice(&inframe3, frame_length),
+            input(inframe1),
+            input(inframe2),
+            input(inframe3),
             outframe,
         );
     }
 }
 
+/// Check the rules of the frei0r specification and the parameter metadata that can be decided
+/// from the plugin type alone.
+///
+/// Evaluated in a constant by [plugin!](crate::plugin), so violations fail the build.
+#[doc(hidden)]
+pub const fn assert_valid<P: Plugin>() {
+    assert!(
+        !(P::Kind::PLUGIN_TYPE == F0R_PLUGIN_TYPE_SOURCE as i32
+            && matches!(P::Pixel::COLOR_MODEL, ColorModel::PACKED32)),
+        "source plugins must not use the PACKED32 color model"
+    );
+    validate_params(P::PARAMS);
+    validate_presets(P::PARAMS, P::PRESETS);
+}
+
+/// Check the presets of a plugin against its parameter list.
+///
+/// The parameters and types set by presets are checked when a preset is applied.
+pub(crate) const fn validate_presets<T>(params: &[ParamInfo<T>], presets: &[Preset]) {
+    if presets.is_empty() {
+        return;
+    }
+    let mut i = 0;
+    while i < params.len() {
+        assert!(
+            !bytes_eq(params[i].name().to_bytes(), PRESET_PARAM.to_bytes()),
+            "a plugin with presets must not have a parameter named preset"
+        );
+        i += 1;
+    }
+    let mut i = 0;
+    while i < presets.len() {
+        let name = presets[i].name.to_bytes();
+        assert!(!name.is_empty(), "preset names must not be empty");
+        let mut k = 0;
+        while k < name.len() {
+            assert!(name[k] != b',', "preset names must not contain a comma");
+            k += 1;
+        }
+        let mut j = 0;
+        while j < i {
+            assert!(
+                !bytes_eq(name, presets[j].name.to_bytes()),
+                "preset names must be unique"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Check a parameter list, at compile time for [Plugin::PARAMS] and when building the list of
+/// [Plugin::params].
+pub(crate) const fn validate_params<T>(params: &[ParamInfo<T>]) {
+    assert!(
+        param_count(params) <= c_int::MAX as usize,
+        "too many parameters, the count must fit in a c_int"
+    );
+    let mut i = 0;
+    while i < params.len() {
+        let param = &params[i];
+        let name = param.name().to_bytes();
+        assert!(!name.is_empty(), "parameter names must not be empty");
+        assert!(
+            param.is_output() || !is_read_only(param.explanation().to_bytes()),
+            "only output parameters may have an explanation starting with (read-only)"
+        );
+        validate_range(param);
+        let mut j = 0;
+        while j < i {
+            assert!(
+                !bytes_eq(name, params[j].name().to_bytes()),
+                "parameter names must be unique"
+            );
+            j += 1;
+        }
+        // The fields of a composite parameter are reported as `<parameter>.<field>`, names in
+        // this form are left to the composite so the expanded names stay unique.
+        if matches!(param.kind(), ParamKind::Composite(_)) {
+            let mut j = 0;
+            while j < params.len() {
+                assert!(
+                    !is_field_name(params[j].name().to_bytes(), name),
+                    "parameter names must not start with the name of a composite parameter and a dot"
+                );
+                j += 1;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Check the range, default and step of a parameter or of a field of a composite parameter.
+pub(crate) const fn validate_range<T>(param: &ParamInfo<T>) {
+    if let Some(range) = param.range() {
+        assert!(
+            range.min.is_finite() && range.max.is_finite() && range.min < range.max,
+            "parameter range must be finite and not empty"
+        );
+        match param.default() {
+            Some(ParamDefault::Double(default)) => assert!(
+                range.min <= default && default <= range.max,
+                "parameter default must be inside its range"
+            ),
+            Some(ParamDefault::Position(default)) => assert!(
+                range.min <= default.x
+                    && default.x <= range.max
+                    && range.min <= default.y
+                    && default.y <= range.max,
+                "parameter default must be inside its range"
+            ),
+            None => {}
+        }
+    }
+    if let Some(step) = param.step() {
+        assert!(
+            step.is_finite() && step > 0.0,
+            "parameter step must be positive"
+        );
+    }
+}
+
+/// Whether `name` has the form `<composite>.<field>`.
+const fn is_field_name(name: &[u8], composite: &[u8]) -> bool {
+    if name.len() <= composite.len() || name[composite.len()] != b'.' {
+        return false;
+    }
+    let mut i = 0;
+    while i < composite.len() {
+        if name[i] != composite[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+pub(crate) const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Convert a NUL terminated string produced by [plugin_info!](crate::plugin_info).
+#[doc(hidden)]
+pub const fn c_str(value: &'static str) -> &'static CStr {
+    match CStr::from_bytes_with_nul(value.as_bytes()) {
+        Ok(value) => value,
+        Err(_) => panic!("package metadata must not contain NUL bytes"),
+    }
+}
+
+/// Like [c_str], but `None` for an empty string.
+#[doc(hidden)]
+pub const fn c_str_non_empty(value: &'static str) -> Option<&'static CStr> {
+    let value = c_str(value);
+    if value.is_empty() { None } else { Some(value) }
+}
+
+/// Parse a version component produced by [plugin_info!](crate::plugin_info).
+#[doc(hidden)]
+pub const fn version(value: &str) -> i32 {
+    let bytes = value.as_bytes();
+    assert!(!bytes.is_empty(), "version component must not be empty");
+    let mut version: i32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "version component must be a number"
+        );
+        version = match version.checked_mul(10) {
+            Some(version) => match version.checked_add((bytes[i] - b'0') as i32) {
+                Some(version) => version,
+                None => panic!("version component does not fit in an i32"),
+            },
+            None => panic!("version component does not fit in an i32"),
+        };
+        i += 1;
+    }
+    version
+}
+
 #[doc(hidden)]
-pub struct Instance<P: Plugin + PluginKindUpdate<P::Kind>> {
-    frame_length: usize,
+pub struct Instance<P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>> {
+    width: usize,
+    height: usize,
+    // Copies of input frames that overlap the output frame, see `unalias`.
+    scratch: [Vec<u32>; 3],
+    // Values of string parameters handed to the application, by frei0r parameter index. The last
+    // buffer holds the current value and at most one previous value is kept, see `cache_string`.
+    strings: Vec<Vec<CString>>,
+    // Parameters set since the last update, see Plugin::params_changed.
+    changed: ParamSet,
+    // By index in params, for parameters with a smoothing policy.
+    smoothers: Vec<Option<Smoother>>,
+    // Time of the previous update.
+    time: Option<f64>,
+    // Index in Plugin::PRESETS of the preset applied last.
+    preset: Option<usize>,
+    table: &'static ParamTable<P>,
     inner: P,
 }
 
 impl<P> Instance<P>
 where
-    P: Plugin + PluginKindUpdate<P::Kind>,
+    P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>,
 {
+    /// Look up the parameter reported to the application at a frei0r index.
+    fn frei0r_param(
+        table: &'static ParamTable<P>,
+        entry_point: &'static str,
+        param_index: c_int,
+    ) -> Option<&'static Frei0rParam> {
+        let param = table.get(param_index);
+        if param.is_none() {
+            diagnostic::report(&Diagnostic::InvalidParamIndex {
+                entry_point,
+                index: param_index,
+            });
+        }
+        param
+    }
+
+    /// Look up the parameter at a frei0r index with its index in `params`, and the field index if
+    /// it is part of a composite parameter.
+    fn param_info(
+        table: &'static ParamTable<P>,
+        entry_point: &'static str,
+        param_index: c_int,
+    ) -> Option<(usize, &'static ParamInfo<P>, Option<usize>)> {
+        let param = Self::frei0r_param(table, entry_point, param_index)?;
+        Some((param.index, &table.params[param.index], param.field))
+    }
+
     pub unsafe fn f0r_get_plugin_info(info: *mut f0r_plugin_info_t) {
-        let info = unsafe { &mut *info };
+        let Some(info) = (unsafe { non_null_mut("f0r_get_plugin_info", "info", info) }) else {
+            return;
+        };
         let our_info = P::info();
 
         info.name = our_info.name.as_ptr();
         info.author = our_info.author.as_ptr();
         info.plugin_type = P::Kind::PLUGIN_TYPE;
-        info.color_model = match our_info.color_model {
+        info.color_model = match P::Pixel::COLOR_MODEL {
             ColorModel::BGRA8888 => F0R_COLOR_MODEL_BGRA8888 as i32,
             ColorModel::RGBA8888 => F0R_COLOR_MODEL_RGBA8888 as i32,
             ColorModel::PACKED32 => F0R_COLOR_MODEL_PACKED32 as i32,
@@ -174,114 +410,231 @@ where
         info.frei0r_version = FREI0R_MAJOR_VERSION as i32;
         info.major_version = our_info.major_version;
         info.minor_version = our_info.minor_version;
-        info.num_params = P::PARAMS.len() as i32;
+        let table = global::params::<P>();
+        info.num_params = (table.len() + table.preset_param().is_some() as usize) as i32;
         if let Some(explanation) = our_info.explanation {
             info.explanation = explanation.as_ptr();
         }
     }
 
     pub unsafe fn f0r_get_param_info(info: *mut f0r_param_info_t, param_index: c_int) {
-        let param_index = param_index as usize;
-
-        let info = unsafe { &mut *info };
-        let our_info: &ParamInfo<P> = &P::PARAMS[param_index];
-
-        info.name = our_info.name().as_ptr();
-        info.type_ = match our_info.kind() {
-            ParamKind::Bool { .. } => F0R_PARAM_BOOL as i32,
-            ParamKind::Double { .. } => F0R_PARAM_DOUBLE as i32,
-            ParamKind::Color { .. } => F0R_PARAM_COLOR as i32,
-            ParamKind::Position { .. } => F0R_PARAM_POSITION as i32,
-            ParamKind::String { .. } => F0R_PARAM_STRING as i32,
+        let Some(info) = (unsafe { non_null_mut("f0r_get_param_info", "info", info) }) else {
+            return;
+        };
+        let table = global::params::<P>();
+        if let Some(explanation) = &table.preset_explanation
+            && table.preset_param() == Some(param_index)
+        {
+            info.name = PRESET_PARAM.as_ptr();
+            info.type_ = F0R_PARAM_STRING as c_int;
+            info.explanation = explanation.as_ptr();
+            return;
+        }
+        let Some(param) = Self::frei0r_param(table, "f0r_get_param_info", param_index) else {
+            return;
         };
-        info.explanation = our_info.explanation().as_ptr();
+        info.name = param.name.as_ptr();
+        info.type_ = param.param_type;
+        info.explanation = param.explanation.as_ptr();
     }
 
-    pub fn new(width: c_uint, height: c_uint) -> Self {
-        let width = width.try_into().unwrap();
-        let height = height.try_into().unwrap();
-        let plugin = P::new(width, height);
-        Self {
-            frame_length: width * height,
-            inner: plugin,
+    pub fn try_new(width: c_uint, height: c_uint) -> Result<Self, BoxError> {
+        DimensionError::check(width, height)?;
+        let width = width as usize;
+        let height = height as usize;
+        let table = global::params::<P>();
+        if table.failed {
+            return Err("the parameter list of the plugin is invalid".into());
         }
+        let plugin = P::try_new(width, height)?;
+        let smoothers = table
+            .params
+            .iter()
+            .map(|param_info| match param_info.smoothing() {
+                Smoothing::None => None,
+                smoothing => Some(Smoother::new(smoothing, get_numeric(param_info, &plugin))),
+            })
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            scratch: Default::default(),
+            strings: Vec::new(),
+            changed: ParamSet::all(table.params.len()),
+            smoothers,
+            time: None,
+            preset: None,
+            table,
+            inner: plugin,
+        })
     }
 
-    pub fn f0r_set_param_value(&mut self, param: f0r_param_t, param_index: c_int) {
-        let param_index = param_index as usize;
-        let param_info: &ParamInfo<P> = &P::PARAMS[param_index];
-        let kind = param_info.kind();
-        match kind {
-            ParamKind::Bool { set, .. } => {
-                let param = unsafe { *(param as *const f0r_param_bool) };
-                set(&mut self.inner, param >= 0.5);
-            }
-            ParamKind::Double { set, .. } => {
-                let param = unsafe { *(param as *const f0r_param_double) };
-                set(&mut self.inner, param);
-            }
-            ParamKind::Color { set, .. } => {
-                let param = unsafe { *(param as *const f0r_param_color) };
-                let color = Color {
-                    r: param.r,
-                    g: param.g,
-                    b: param.b,
-                };
-                set(&mut self.inner, &color);
-            }
-            ParamKind::Position { set, .. } => {
-                let param = unsafe { *(param as *const f0r_param_position) };
-                let position = Position {
-                    x: param.x,
-                    y: param.y,
-                };
-                set(&mut self.inner, &position);
+    pub fn frame_length(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// The number of string buffers kept for a frei0r index, see `cache_string`.
+    pub fn string_buffers(&self, param_index: c_int) -> usize {
+        usize::try_from(param_index)
+            .ok()
+            .and_then(|index| self.strings.get(index))
+            .map_or(0, Vec::len)
+    }
+
+    pub unsafe fn f0r_set_param_value(&mut self, param: f0r_param_t, param_index: c_int) {
+        const ENTRY_POINT: &str = "f0r_set_param_value";
+        release_strings(&mut self.strings, param_index);
+        if self.table.preset_param() == Some(param_index) {
+            if let Some(name) = unsafe { read_string(ENTRY_POINT, param) } {
+                self.apply_preset(name);
             }
-            ParamKind::String { set, .. } => {
-                let param = unsafe { *(param as *const f0r_param_string) };
-                let string = unsafe { CStr::from_ptr(param) };
-                set(&mut self.inner, string);
+            return;
+        }
+        let Some((index, param_info, component)) =
+            Self::param_info(self.table, ENTRY_POINT, param_index)
+        else {
+            return;
+        };
+        if param.is_null() {
+            diagnostic::report(&Diagnostic::NullPointer {
+                entry_point: ENTRY_POINT,
+                argument: "param",
+            });
+            return;
+        }
+        // Hosts commonly set every parameter they read, so this is not reported.
+        if param_info.is_output() {
+            return;
+        }
+        let set = match (param_info.kind(), component, &mut self.smoothers[index]) {
+            (ParamKind::Composite(composite), Some(field), _) => unsafe {
+                composite.set_field(&mut self.inner, field, param, param_index)
+            },
+            // The plugin sees the value in the following updates. A NaN target would never be
+            // reached, it is ignored even without a range.
+            (_, _, Some(smoother)) => {
+                if let Some(value) = unsafe { read_numeric(param_info, param) }
+                    && !value.iter().any(|value| value.is_nan())
+                {
+                    smoother.set_target(value);
+                }
+                false
             }
+            _ => unsafe { set_param(param_info, &mut self.inner, param, param_index) },
         };
+        if set {
+            self.changed.insert(index);
+        }
     }
 
-    pub fn f0r_get_param_value(&self, param: f0r_param_t, param_index: c_int) {
-        let param_index = param_index as usize;
-        let param_info: &ParamInfo<P> = &P::PARAMS[param_index];
-        let kind = param_info.kind();
-        match kind {
-            ParamKind::Bool { get, .. } => {
-                let param = unsafe { &mut *(param as *mut f0r_param_bool) };
-                *param = if get(&self.inner) { 1.0 } else { 0.0 };
-            }
-            ParamKind::Double { get, .. } => {
-                let param = unsafe { &mut *(param as *mut f0r_param_double) };
-                *param = get(&self.inner);
-            }
-            ParamKind::Color { get, .. } => {
-                let param = unsafe { &mut *(param as *mut f0r_param_color) };
-                let color = get(&self.inner);
-                param.r = color.r;
-                param.g = color.g;
-                param.b = color.b;
+    pub unsafe fn f0r_get_param_value(&mut self, param: f0r_param_t, param_index: c_int) {
+        const ENTRY_POINT: &str = "f0r_get_param_value";
+        if self.table.preset_param() == Some(param_index) {
+            let Some(param) = (unsafe { non_null_mut(ENTRY_POINT, "param", param.cast()) }) else {
+                return;
+            };
+            let name = self.preset.map_or(c"", |index| P::PRESETS[index].name);
+            *param = cache_string(
+                string_buffer(&mut self.strings, param_index as usize),
+                name.to_bytes(),
+            );
+            return;
+        }
+        let Some((index, param_info, component)) =
+            Self::param_info(self.table, ENTRY_POINT, param_index)
+        else {
+            return;
+        };
+        if param.is_null() {
+            diagnostic::report(&Diagnostic::NullPointer {
+                entry_point: ENTRY_POINT,
+                argument: "param",
+            });
+            return;
+        }
+        match (param_info.kind(), component, &self.smoothers[index]) {
+            (ParamKind::Composite(composite), Some(field), _) => unsafe {
+                composite.get_field(&self.inner, field, param)
+            },
+            (_, _, Some(smoother)) => unsafe {
+                write_numeric(param_info, param, smoother.target())
+            },
+            _ => {
+                let string = string_buffer(&mut self.strings, param_index as usize);
+                unsafe { get_param(param_info, &self.inner, param, string) }
             }
-            ParamKind::Position { get, .. } => {
-                let param = unsafe { &mut *(param as *mut f0r_param_position) };
-                let position = get(&self.inner);
-                param.x = position.x;
-                param.y = position.y;
+        }
+    }
+
+    // Sets the values of a preset through f0r_set_param_value, as if the application set them.
+    fn apply_preset(&mut self, name: &CStr) {
+        let Some(index) = P::PRESETS.iter().position(|preset| preset.name == name) else {
+            diagnostic::report(&Diagnostic::UnknownPreset { value: name });
+            return;
+        };
+        let preset = &P::PRESETS[index];
+        for (param_name, value) in preset.values {
+            if !self.set_value(param_name, &ParamValue::from(*value)) {
+                diagnostic::report(&Diagnostic::InvalidPresetValue {
+                    preset: preset.name,
+                    param: param_name,
+                });
             }
-            ParamKind::String { get, .. } => {
-                let param = unsafe { &mut *(param as *mut f0r_param_string) };
-                // We are casting away constness here. This should be fine since quoting the
-                // comment found in the original header, "If the caller needs to modify the
-                // value, it should make a copy of it and modify before calling
-                // f0r_set_param_value()."
-                *param = get(&self.inner).as_ptr() as f0r_param_string;
+        }
+        self.preset = Some(index);
+    }
+
+    // Sets the parameter reported to the application as `name` through f0r_set_param_value.
+    //
+    // Returns false if there is no such parameter of the type of `value`.
+    fn set_value(&mut self, name: &CStr, value: &ParamValue) -> bool {
+        let string;
+        let (param_type, mut wire) = match value {
+            ParamValue::Bool(value) => (
+                F0R_PARAM_BOOL,
+                WireValue {
+                    bool: if *value { 1.0 } else { 0.0 },
+                },
+            ),
+            ParamValue::Double(value) => (F0R_PARAM_DOUBLE, WireValue { double: *value }),
+            ParamValue::Color(Color { r, g, b }) => (
+                F0R_PARAM_COLOR,
+                WireValue {
+                    color: f0r_param_color {
+                        r: *r,
+                        g: *g,
+                        b: *b,
+                    },
+                },
+            ),
+            ParamValue::Position(Position { x, y }) => (
+                F0R_PARAM_POSITION,
+                WireValue {
+                    position: f0r_param_position { x: *x, y: *y },
+                },
+            ),
+            ParamValue::String(value) => {
+                let Ok(value) = CString::new(value.as_str()) else {
+                    return false;
+                };
+                string = value;
+                (
+                    F0R_PARAM_STRING,
+                    WireValue {
+                        string: string.as_ptr() as f0r_param_string,
+                    },
+                )
             }
         };
+        let Some(param_index) = self.table.find(name, param_type as c_int) else {
+            return false;
+        };
+        let param = &mut wire as *mut WireValue as f0r_param_t;
+        unsafe { self.f0r_set_param_value(param, param_index) };
+        true
     }
 
+    /// Returns false if the call was ignored because a required frame is null.
     pub unsafe fn f0r_update2(
         &mut self,
         time: f64,
@@ -289,27 +642,589 @@ where
         inframe2: *const u32,
         inframe3: *const u32,
         outframe: *mut u32,
-    ) {
-        if outframe.is_null() {
-            panic!("unexpected null output frame");
+    ) -> bool {
+        let inframes = [
+            ("inframe1", inframe1),
+            ("inframe2", inframe2),
+            ("inframe3", inframe3),
+        ];
+        let missing = inframes[..P::Kind::INPUT_COUNT]
+            .iter()
+            .find(|(_, frame)| frame.is_null())
+            .map(|(argument, _)| *argument)
+            .or(outframe.is_null().then_some("outframe"));
+        if let Some(argument) = missing {
+            diagnostic::report(&Diagnostic::NullPointer {
+                entry_point: "f0r_update2",
+                argument,
+            });
+            return false;
         }
-        let outframe = unsafe { std::slice::from_raw_parts_mut(outframe, self.frame_length) };
-        <P as PluginKindUpdate<P::Kind>>::update(
+        self.load_resources();
+        self.smooth_params(time);
+        if !self.changed.is_empty() {
+            self.inner.params_changed(&self.changed);
+            self.changed.clear();
+        }
+        // Hosts may process in place (inframe == outframe). A shared and a mutable slice over
+        // the same memory is undefined behavior, so overlapping inputs are copied first.
+        let [inframe1, inframe2, inframe3] = unsafe {
+            [
+                self.unalias(0, inframe1, outframe),
+                self.unalias(1, inframe2, outframe),
+                self.unalias(2, inframe3, outframe),
+            ]
+        };
+        let frame_length = self.frame_length();
+        // Inputs the plugin kind does not use are never read, they may be stale or alias the
+        // output frame.
+        let inframes = [inframe1, inframe2, inframe3];
+        let inframes = std::array::from_fn(|index| {
+            (index < P::Kind::INPUT_COUNT).then(|| {
+                let data = unsafe { std::slice::from_raw_parts(inframes[index], frame_length) };
+                Frame::new(self.width, self.height, data)
+            })
+        });
+        let outframe = unsafe { std::slice::from_raw_parts_mut(outframe, frame_length) };
+        let outframe = FrameMut::new(self.width, self.height, outframe);
+        <P as PluginKindUpdate<<P as Plugin>::Kind>>::update(
             &mut self.inner,
-            self.frame_length,
             time,
-            inframe1,
-            inframe2,
-            inframe3,
+            inframes,
             outframe,
         );
+        true
+    }
+
+    // Moves smoothed parameters towards the values set by the application.
+    fn smooth_params(&mut self, time: f64) {
+        // Jump to the targets on the first update and when going back in time.
+        let elapsed = self
+            .time
+            .map(|previous| time - previous)
+            .filter(|elapsed| *elapsed >= 0.0);
+        self.time = Some(time);
+        for (index, smoother) in self.smoothers.iter_mut().enumerate() {
+            if let Some(smoother) = smoother
+                && smoother.advance(elapsed)
+            {
+                set_numeric(&self.table.params[index], &mut self.inner, smoother.value());
+                self.changed.insert(index);
+            }
+        }
+    }
+
+    // Loads the files of path parameters set since the last update.
+    fn load_resources(&mut self) {
+        for param_info in self.table.params {
+            if let ParamKind::Path { get_mut, .. } = param_info.kind() {
+                get_mut(&mut self.inner).load_pending();
+            }
+        }
     }
+
+    // Returns `inframe`, or a pointer to a copy of it in scratch buffer `index` if it overlaps
+    // `outframe`.
+    unsafe fn unalias(
+        &mut self,
+        index: usize,
+        inframe: *const u32,
+        outframe: *mut u32,
+    ) -> *const u32 {
+        let frame_length = self.frame_length();
+        if index >= P::Kind::INPUT_COUNT || !overlaps(inframe, outframe, frame_length) {
+            return inframe;
+        }
+        let scratch = &mut self.scratch[index];
+        scratch.resize(frame_length, 0);
+        unsafe { std::ptr::copy(inframe, scratch.as_mut_ptr(), frame_length) };
+        scratch.as_ptr()
+    }
+}
+
+/// Name of the string parameter applying [Plugin::PRESETS], following the other parameters.
+pub(crate) const PRESET_PARAM: &CStr = c"preset";
+
+/// Start of the explanation of the preset parameter, followed by the names of the presets.
+pub(crate) const PRESET_EXPLANATION: &CStr = c"Apply a preset, one of:";
+
+/// Storage for a parameter value in any frei0r type.
+#[repr(C)]
+union WireValue {
+    bool: f0r_param_bool,
+    double: f0r_param_double,
+    color: f0r_param_color,
+    position: f0r_param_position,
+    string: f0r_param_string,
+}
+
+/// The parameter list of a plugin with the frei0r parameters it expands to, built once per
+/// plugin by [global::params].
+pub(crate) struct ParamTable<T: 'static> {
+    pub(crate) params: &'static [ParamInfo<T>],
+    // By frei0r index, fields of composite parameters follow each other.
+    frei0r: Vec<Frei0rParam>,
+    // Explanation of the preset parameter, if the plugin has presets.
+    preset_explanation: Option<CString>,
+    pub(crate) failed: bool,
 }
 
-fn frame_to_slice(frame: &*const u32, length: usize) -> &[u32] {
-    if frame.is_null() {
-        panic!("Unexpected null frame");
+/// A parameter as reported to the application.
+struct Frei0rParam {
+    // `<parameter>.<field>` for fields of composite parameters.
+    name: Cow<'static, CStr>,
+    param_type: c_int,
+    explanation: Cow<'static, CStr>,
+    // Index in the parameter list.
+    index: usize,
+    // Index of the field, for fields of composite parameters.
+    field: Option<usize>,
+}
+
+impl<T> ParamTable<T> {
+    pub(crate) fn new(params: &'static [ParamInfo<T>], presets: &[Preset]) -> Self {
+        let mut frei0r = Vec::with_capacity(param_count(params));
+        for (index, param_info) in params.iter().enumerate() {
+            let ParamKind::Composite(composite) = param_info.kind() else {
+                frei0r.push(Frei0rParam {
+                    name: Cow::Borrowed(param_info.name()),
+                    param_type: param_type(param_info),
+                    explanation: reported_explanation(param_info, param_info.explanation()),
+                    index,
+                    field: None,
+                });
+                continue;
+            };
+            for (field, field_name) in composite.names().enumerate() {
+                let mut explanation = composite.field_explanation(field);
+                if explanation.is_empty() {
+                    explanation = param_info.explanation();
+                }
+                frei0r.push(Frei0rParam {
+                    name: Cow::Owned(concat(&[
+                        param_info.name().to_bytes(),
+                        b".",
+                        field_name.to_bytes(),
+                    ])),
+                    param_type: composite.field_type(field),
+                    explanation: reported_explanation(param_info, explanation),
+                    index,
+                    field: Some(field),
+                });
+            }
+        }
+        let preset_explanation = (!presets.is_empty()).then(|| {
+            let mut parts = vec![PRESET_EXPLANATION.to_bytes()];
+            for (index, preset) in presets.iter().enumerate() {
+                parts.push(if index == 0 { b" " } else { b", " });
+                parts.push(preset.name.to_bytes());
+            }
+            concat(&parts)
+        });
+        ParamTable {
+            params,
+            frei0r,
+            preset_explanation,
+            failed: false,
+        }
+    }
+
+    /// An empty table standing in for a parameter list that failed validation, the failure is
+    /// only reported once.
+    pub(crate) fn failed() -> Self {
+        ParamTable {
+            params: &[],
+            frei0r: Vec::new(),
+            preset_explanation: None,
+            failed: true,
+        }
+    }
+
+    /// Number of frei0r parameters, without the preset parameter.
+    fn len(&self) -> usize {
+        self.frei0r.len()
+    }
+
+    /// The frei0r index of the preset parameter, if the plugin has presets.
+    fn preset_param(&self) -> Option<c_int> {
+        self.preset_explanation
+            .is_some()
+            .then_some(self.len() as c_int)
+    }
+
+    fn get(&self, param_index: c_int) -> Option<&Frei0rParam> {
+        usize::try_from(param_index)
+            .ok()
+            .and_then(|index| self.frei0r.get(index))
+    }
+
+    /// The frei0r index of the parameter reported to the application as `name`, if it has type
+    /// `param_type`.
+    fn find(&self, name: &CStr, param_type: c_int) -> Option<c_int> {
+        let index = self.frei0r.iter().position(|param| *param.name == *name)?;
+        (self.frei0r[index].param_type == param_type).then_some(index as c_int)
+    }
+}
+
+/// The explanation reported for a parameter, marking output parameters as read-only.
+fn reported_explanation<T>(
+    param_info: &ParamInfo<T>,
+    explanation: &'static CStr,
+) -> Cow<'static, CStr> {
+    if !param_info.is_output() {
+        Cow::Borrowed(explanation)
+    } else if explanation.is_empty() {
+        Cow::Borrowed(READ_ONLY)
     } else {
-        unsafe { std::slice::from_raw_parts(*frame, length) }
+        Cow::Owned(concat(&[
+            READ_ONLY.to_bytes(),
+            b" ",
+            explanation.to_bytes(),
+        ]))
+    }
+}
+
+/// Concatenate the bytes of C strings and separators.
+fn concat(parts: &[&[u8]]) -> CString {
+    CString::new(parts.concat()).expect("names and explanations contain no NUL")
+}
+
+/// Read a string value from the application.
+unsafe fn read_string<'a>(entry_point: &'static str, param: f0r_param_t) -> Option<&'a CStr> {
+    let param = unsafe { non_null_mut(entry_point, "param", param as *mut f0r_param_string) }?;
+    if param.is_null() {
+        diagnostic::report(&Diagnostic::NullPointer {
+            entry_point,
+            argument: "*param",
+        });
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(*param) })
+}
+
+/// Start of the explanation of output parameters, see [ParamInfo::as_output].
+pub(crate) const READ_ONLY: &CStr = c"(read-only)";
+
+/// Whether an explanation starts with [READ_ONLY], as reported for output parameters.
+pub(crate) const fn is_read_only(explanation: &[u8]) -> bool {
+    let marker = READ_ONLY.to_bytes();
+    if explanation.len() < marker.len() {
+        return false;
+    }
+    let (start, rest) = explanation.split_at(marker.len());
+    bytes_eq(start, marker) && (rest.is_empty() || rest[0] == b' ')
+}
+
+/// Set `target` from a parameter value received from the application.
+///
+/// Returns false if the value was ignored.
+pub(crate) unsafe fn set_param<T>(
+    param_info: &ParamInfo<T>,
+    target: &mut T,
+    param: f0r_param_t,
+    param_index: c_int,
+) -> bool {
+    const ENTRY_POINT: &str = "f0r_set_param_value";
+    match param_info.kind() {
+        ParamKind::Bool { set, .. } => {
+            let param = unsafe { *(param as *const f0r_param_bool) };
+            set(target, param >= 0.5);
+        }
+        ParamKind::Double { set, .. } => {
+            let Some([value, _]) = (unsafe { read_numeric(param_info, param) }) else {
+                return false;
+            };
+            set(target, value);
+        }
+        ParamKind::Int { set, .. } => {
+            let param = unsafe { *(param as *const f0r_param_double) };
+            let range = param_info.range().expect("integer parameters have a range");
+            if param.is_nan() {
+                return false;
+            }
+            set(target, range.denormalize(param).round() as i32);
+        }
+        ParamKind::Color { set, .. } => {
+            let param = unsafe { *(param as *const f0r_param_color) };
+            let color = Color {
+                r: param.r,
+                g: param.g,
+                b: param.b,
+            };
+            set(target, &color);
+        }
+        ParamKind::Position { set, .. } => {
+            let Some([x, y]) = (unsafe { read_numeric(param_info, param) }) else {
+                return false;
+            };
+            set(target, &Position { x, y });
+        }
+        ParamKind::String { set, .. } => {
+            let Some(string) = (unsafe { read_string(ENTRY_POINT, param) }) else {
+                return false;
+            };
+            set(target, string);
+        }
+        ParamKind::OwnedString { set, .. } => {
+            let Some(string) = (unsafe { read_string(ENTRY_POINT, param) }) else {
+                return false;
+            };
+            set(target, string.to_string_lossy().into_owned());
+        }
+        ParamKind::Path { get_mut, .. } => {
+            let Some(path) = (unsafe { read_string(ENTRY_POINT, param) }) else {
+                return false;
+            };
+            get_mut(target).set_path(path);
+        }
+        ParamKind::Choice(choice) => {
+            let index = match choice.encoding() {
+                ChoiceEncoding::String => {
+                    let Some(name) = (unsafe { read_string(ENTRY_POINT, param) }) else {
+                        return false;
+                    };
+                    let index = choice.decode_name(name);
+                    if index.is_none() {
+                        diagnostic::report(&Diagnostic::UnknownChoice {
+                            index: param_index,
+                            value: name,
+                        });
+                    }
+                    index
+                }
+                ChoiceEncoding::Double => {
+                    choice.decode_double(unsafe { *(param as *const f0r_param_double) })
+                }
+            };
+            let Some(index) = index else {
+                return false;
+            };
+            choice.set(target, index);
+        }
+        // Rejected by ParamInfo::new_composite.
+        ParamKind::Composite(_) => return false,
+    };
+    true
+}
+
+/// Write the value of a parameter of `target` for the application.
+///
+/// String values are copied to `string`, the buffers owned by the instance for this parameter.
+pub(crate) unsafe fn get_param<T>(
+    param_info: &ParamInfo<T>,
+    target: &T,
+    param: f0r_param_t,
+    string: &mut Vec<CString>,
+) {
+    match param_info.kind() {
+        ParamKind::Bool { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_bool) };
+            *param = if get(target) { 1.0 } else { 0.0 };
+        }
+        ParamKind::Double { get, .. } => unsafe {
+            write_numeric(param_info, param, [get(target), 0.0])
+        },
+        ParamKind::Int { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_double) };
+            let range = param_info.range().expect("integer parameters have a range");
+            *param = range.normalize(get(target) as f64);
+        }
+        ParamKind::Color { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_color) };
+            let color = get(target);
+            param.r = color.r;
+            param.g = color.g;
+            param.b = color.b;
+        }
+        ParamKind::Position { get, .. } => {
+            let position = get(target);
+            unsafe { write_numeric(param_info, param, [position.x, position.y]) }
+        }
+        ParamKind::String { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_string) };
+            *param = cache_string(string, get(target).to_bytes());
+        }
+        ParamKind::OwnedString { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_string) };
+            let value = get(target).as_bytes();
+            // A C string ends at the first NUL.
+            let value = value.split(|&byte| byte == 0).next().unwrap_or_default();
+            *param = cache_string(string, value);
+        }
+        ParamKind::Path { get, .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_string) };
+            *param = cache_string(string, get(target).path().to_bytes());
+        }
+        ParamKind::Choice(choice) => {
+            let index = choice.get(target);
+            match choice.encoding() {
+                ChoiceEncoding::String => {
+                    let param = unsafe { &mut *(param as *mut f0r_param_string) };
+                    let name = choice.names().nth(index).expect("valid choice index");
+                    *param = name.as_ptr() as f0r_param_string;
+                }
+                ChoiceEncoding::Double => {
+                    let param = unsafe { &mut *(param as *mut f0r_param_double) };
+                    *param = choice.encode_double(index);
+                }
+            }
+        }
+        // Rejected by ParamInfo::new_composite.
+        ParamKind::Composite(_) => {}
+    };
+}
+
+/// Read the value of a double or position parameter from the application, mapped into its
+/// range. Doubles only use the first component.
+///
+/// Returns `None` if the value is ignored.
+unsafe fn read_numeric<T>(param_info: &ParamInfo<T>, param: f0r_param_t) -> Option<[f64; 2]> {
+    let mut value = match param_info.kind() {
+        ParamKind::Double { .. } => [unsafe { *(param as *const f0r_param_double) }, 0.0],
+        ParamKind::Position { .. } => {
+            let param = unsafe { *(param as *const f0r_param_position) };
+            [param.x, param.y]
+        }
+        _ => unreachable!("not a double or position parameter"),
+    };
+    if let Some(range) = param_info.range() {
+        if value.iter().any(|value| value.is_nan()) {
+            return None;
+        }
+        value = value.map(|value| range.denormalize(value));
+    }
+    Some(value)
+}
+
+/// Write the value of a double or position parameter for the application, the inverse of
+/// [read_numeric].
+unsafe fn write_numeric<T>(param_info: &ParamInfo<T>, param: f0r_param_t, mut value: [f64; 2]) {
+    if let Some(range) = param_info.range() {
+        value = value.map(|value| range.normalize(value));
+    }
+    match param_info.kind() {
+        ParamKind::Double { .. } => unsafe { *(param as *mut f0r_param_double) = value[0] },
+        ParamKind::Position { .. } => {
+            let param = unsafe { &mut *(param as *mut f0r_param_position) };
+            param.x = value[0];
+            param.y = value[1];
+        }
+        _ => unreachable!("not a double or position parameter"),
     }
 }
+
+/// The value of a double or position parameter of `target`, in the layout of [read_numeric].
+fn get_numeric<T>(param_info: &ParamInfo<T>, target: &T) -> [f64; 2] {
+    match param_info.kind() {
+        ParamKind::Double { get, .. } => [get(target), 0.0],
+        ParamKind::Position { get, .. } => {
+            let position = get(target);
+            [position.x, position.y]
+        }
+        _ => unreachable!("not a double or position parameter"),
+    }
+}
+
+/// Set a double or position parameter of `target`, in the layout of [read_numeric].
+fn set_numeric<T>(param_info: &ParamInfo<T>, target: &mut T, [x, y]: [f64; 2]) {
+    match param_info.kind() {
+        ParamKind::Double { set, .. } => set(target, x),
+        ParamKind::Position { set, .. } => set(target, &Position { x, y }),
+        _ => unreachable!("not a double or position parameter"),
+    }
+}
+
+/// The buffers of the string values handed to the application for a frei0r index.
+fn string_buffer(strings: &mut Vec<Vec<CString>>, param_index: usize) -> &mut Vec<CString> {
+    if strings.len() <= param_index {
+        strings.resize(param_index + 1, Vec::new());
+    }
+    &mut strings[param_index]
+}
+
+/// Drop the buffers of a frei0r index replaced since the application last set it.
+fn release_strings(strings: &mut [Vec<CString>], param_index: c_int) {
+    if let Some(buffers) = usize::try_from(param_index)
+        .ok()
+        .and_then(|index| strings.get_mut(index))
+    {
+        buffers.drain(..buffers.len().saturating_sub(1));
+    }
+}
+
+/// Copy a string value into the buffer handed to the application.
+///
+/// A buffer is only added when the value changed. The buffer it replaces is kept, so a pointer
+/// handed to the application stays valid until it sets the parameter, reads two further values
+/// or destructs the instance, see [release_strings]. Older buffers are dropped, values that keep
+/// changing, e.g. of output parameters, would otherwise pile up.
+fn cache_string(buffers: &mut Vec<CString>, value: &[u8]) -> f0r_param_string {
+    if buffers
+        .last()
+        .is_none_or(|cached| cached.to_bytes() != value)
+    {
+        buffers.push(CString::new(value).expect("C string values contain no NUL"));
+        buffers.drain(..buffers.len().saturating_sub(2));
+    }
+    // We are casting away constness here. This should be fine since quoting the comment found
+    // in the original header, "If the caller needs to modify the value, it should make a copy
+    // of it and modify before calling f0r_set_param_value()."
+    buffers.last().expect("cached above").as_ptr() as f0r_param_string
+}
+
+/// The frei0r type of a parameter that is not a composite.
+pub(crate) fn param_type<T>(param_info: &ParamInfo<T>) -> c_int {
+    match param_info.kind() {
+        ParamKind::Bool { .. } => F0R_PARAM_BOOL as c_int,
+        ParamKind::Double { .. } | ParamKind::Int { .. } => F0R_PARAM_DOUBLE as c_int,
+        ParamKind::Color { .. } => F0R_PARAM_COLOR as c_int,
+        ParamKind::Position { .. } => F0R_PARAM_POSITION as c_int,
+        ParamKind::String { .. } | ParamKind::OwnedString { .. } | ParamKind::Path { .. } => {
+            F0R_PARAM_STRING as c_int
+        }
+        ParamKind::Choice(choice) => match choice.encoding() {
+            ChoiceEncoding::String => F0R_PARAM_STRING as c_int,
+            ChoiceEncoding::Double => F0R_PARAM_DOUBLE as c_int,
+        },
+        // Rejected by ParamInfo::new_composite.
+        ParamKind::Composite(_) => F0R_PARAM_DOUBLE as c_int,
+    }
+}
+
+/// Number of frei0r parameters `params` expand to.
+pub(crate) const fn param_count<T>(params: &[ParamInfo<T>]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < params.len() {
+        count += params[i].frei0r_count();
+        i += 1;
+    }
+    count
+}
+
+fn overlaps(inframe: *const u32, outframe: *const u32, length: usize) -> bool {
+    let size = length * size_of::<u32>();
+    let inframe = inframe as usize;
+    let outframe = outframe as usize;
+    inframe < outframe.wrapping_add(size) && outframe < inframe.wrapping_add(size)
+}
+
+/// Convert a pointer received from the application to a reference, reporting null pointers.
+pub(crate) unsafe fn non_null_mut<'a, T>(
+    entry_point: &'static str,
+    argument: &'static str,
+    ptr: *mut T,
+) -> Option<&'a mut T> {
+    let reference = unsafe { ptr.as_mut() };
+    if reference.is_none() {
+        diagnostic::report(&Diagnostic::NullPointer {
+            entry_point,
+            argument,
+        });
+    }
+    reference
+}
+
+fn input<P: Pixel>(frame: Option<Frame<'_, P>>) -> Frame<'_, P> {
+    frame.expect("input frames are checked by Instance::f0r_update2")
+}
@@ -0,0 +1,221 @@
+use crate::{ColorModel, Pixel};
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice::{ChunksExact, ChunksExactMut},
+};
+
+/// A read-only video frame: `width * height` pixels stored row-wise, top-most row first.
+#[derive(Debug)]
+pub struct Frame<'a, P: Pixel> {
+    width: usize,
+    height: usize,
+    data: &'a [u32],
+    pixel: PhantomData<P>,
+}
+
+impl<P: Pixel> Clone for Frame<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Pixel> Copy for Frame<'_, P> {}
+
+impl<'a, P: Pixel> Frame<'a, P> {
+    /// Create a frame view over `data`.
+    ///
+    /// # Panics
+    ///
+    /// If `data` does not hold exactly `width * height` pixels.
+    pub fn new(width: usize, height: usize, data: &'a [u32]) -> Self {
+        assert_eq!(data.len(), width * height, "frame size mismatch");
+        Frame {
+            width,
+            height,
+            data,
+            pixel: PhantomData,
+        }
+    }
+
+    /// Width of the frame in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the frame in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The color model of the pixels.
+    pub fn color_model(&self) -> ColorModel {
+        P::COLOR_MODEL
+    }
+
+    /// All pixels of the frame.
+    pub fn as_slice(&self) -> &'a [P] {
+        P::from_u32_slice(self.data)
+    }
+
+    /// All pixels of the frame as `u32` values, as passed by the application.
+    pub fn as_u32_slice(&self) -> &'a [u32] {
+        self.data
+    }
+
+    /// All pixels of the frame as bytes, in memory order.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        P::slice_as_bytes(self.as_slice())
+    }
+
+    /// The pixel at column `x` and row `y`, `None` if out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<P> {
+        (x < self.width && y < self.height).then(|| self.as_slice()[y * self.width + x])
+    }
+
+    /// The pixels of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &'a [P] {
+        &self.as_slice()[y * self.width..][..self.width]
+    }
+
+    /// Iterate over the rows of the frame, top-most first.
+    pub fn rows(&self) -> ChunksExact<'a, P> {
+        self.as_slice().chunks_exact(self.width.max(1))
+    }
+}
+
+impl<P: Pixel> Index<(usize, usize)> for Frame<'_, P> {
+    type Output = P;
+
+    fn index(&self, (x, y): (usize, usize)) -> &P {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        &self.as_slice()[y * self.width + x]
+    }
+}
+
+/// A writable video frame: `width * height` pixels stored row-wise, top-most row first.
+#[derive(Debug)]
+pub struct FrameMut<'a, P: Pixel> {
+    width: usize,
+    height: usize,
+    data: &'a mut [u32],
+    pixel: PhantomData<P>,
+}
+
+impl<'a, P: Pixel> FrameMut<'a, P> {
+    /// Create a writable frame view over `data`.
+    ///
+    /// # Panics
+    ///
+    /// If `data` does not hold exactly `width * height` pixels.
+    pub fn new(width: usize, height: usize, data: &'a mut [u32]) -> Self {
+        assert_eq!(data.len(), width * height, "frame size mismatch");
+        FrameMut {
+            width,
+            height,
+            data,
+            pixel: PhantomData,
+        }
+    }
+
+    /// Width of the frame in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the frame in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The color model of the pixels.
+    pub fn color_model(&self) -> ColorModel {
+        P::COLOR_MODEL
+    }
+
+    /// A read-only view of this frame.
+    pub fn as_frame(&self) -> Frame<'_, P> {
+        Frame::new(self.width, self.height, self.data)
+    }
+
+    /// All pixels of the frame.
+    pub fn as_slice(&self) -> &[P] {
+        P::from_u32_slice(self.data)
+    }
+
+    /// All pixels of the frame, mutably.
+    pub fn as_mut_slice(&mut self) -> &mut [P] {
+        P::from_u32_slice_mut(self.data)
+    }
+
+    /// All pixels of the frame as mutable bytes, in memory order.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        P::slice_as_bytes_mut(self.as_mut_slice())
+    }
+
+    /// Convert into the underlying `u32` pixels, as passed by the application.
+    pub fn into_u32_slice(self) -> &'a mut [u32] {
+        self.data
+    }
+
+    /// The pixel at column `x` and row `y`, `None` if out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<P> {
+        self.as_frame().get(x, y)
+    }
+
+    /// Set the pixel at column `x` and row `y`.
+    ///
+    /// # Panics
+    ///
+    /// If `(x, y)` is out of bounds.
+    pub fn put(&mut self, x: usize, y: usize, pixel: P) {
+        self[(x, y)] = pixel;
+    }
+
+    /// Set every pixel of the frame.
+    pub fn fill(&mut self, pixel: P) {
+        self.as_mut_slice().fill(pixel);
+    }
+
+    /// The pixels of row `y`, mutably.
+    ///
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> &mut [P] {
+        let width = self.width;
+        &mut self.as_mut_slice()[y * width..][..width]
+    }
+
+    /// Iterate over the rows of the frame, top-most first.
+    pub fn rows(&self) -> ChunksExact<'_, P> {
+        self.as_slice().chunks_exact(self.width.max(1))
+    }
+
+    /// Iterate mutably over the rows of the frame, top-most first.
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, P> {
+        let width = self.width.max(1);
+        self.as_mut_slice().chunks_exact_mut(width)
+    }
+}
+
+impl<P: Pixel> Index<(usize, usize)> for FrameMut<'_, P> {
+    type Output = P;
+
+    fn index(&self, (x, y): (usize, usize)) -> &P {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        &self.as_slice()[y * self.width + x]
+    }
+}
+
+impl<P: Pixel> IndexMut<(usize, usize)> for FrameMut<'_, P> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut P {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        let width = self.width;
+        &mut self.as_mut_slice()[y * width + x]
+    }
+}
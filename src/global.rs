@@ -0,0 +1,131 @@
+use crate::ffi::{self, ParamTable};
+use crate::{BoxError, Plugin, PluginGlobal};
+use std::{
+    any::{Any, TypeId},
+    panic,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Library wide state of a [PluginGlobal] type, shared by the instances of the plugins using it.
+struct Entry {
+    global: TypeId,
+    // Number of f0r_init calls not yet matched by f0r_deinit.
+    init_count: usize,
+    state: Arc<dyn Any + Send + Sync>,
+}
+
+// Statics can not be generic, so every state in the library is kept here.
+static GLOBALS: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+fn globals() -> MutexGuard<'static, Vec<Entry>> {
+    GLOBALS.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn position<G: PluginGlobal>(globals: &[Entry]) -> Option<usize> {
+    globals
+        .iter()
+        .position(|entry| entry.global == TypeId::of::<G>())
+}
+
+/// Handle `f0r_init`, initializing the state on the first call.
+pub(crate) fn init<G: PluginGlobal>() -> Result<(), BoxError> {
+    let mut globals = globals();
+    match position::<G>(&globals) {
+        Some(index) => globals[index].init_count += 1,
+        None => globals.push(Entry {
+            global: TypeId::of::<G>(),
+            init_count: 1,
+            state: Arc::new(G::init()?),
+        }),
+    }
+    Ok(())
+}
+
+/// Handle `f0r_deinit`, deinitializing the state once every `f0r_init` has been matched.
+pub(crate) fn deinit<G: PluginGlobal>() {
+    let entry = {
+        let mut globals = globals();
+        let Some(index) = position::<G>(&globals) else {
+            return;
+        };
+        let entry = &mut globals[index];
+        entry.init_count = entry.init_count.saturating_sub(1);
+        if entry.init_count > 0 {
+            return;
+        }
+        globals.swap_remove(index)
+    };
+    let state = entry
+        .state
+        .downcast::<G>()
+        .expect("global state stored under its type id");
+    // Instances still alive after f0r_deinit keep their reference, the state is dropped with the
+    // last of them instead.
+    if let Ok(state) = Arc::try_unwrap(state) {
+        state.deinit();
+    }
+}
+
+/// The state returned by [PluginGlobal::get].
+///
+/// Initialized on demand if the application did not call `f0r_init`.
+pub(crate) fn get<G: PluginGlobal>() -> Result<Arc<G>, BoxError> {
+    let mut globals = globals();
+    let state = match position::<G>(&globals) {
+        Some(index) => globals[index].state.clone(),
+        None => {
+            let state: Arc<dyn Any + Send + Sync> = Arc::new(G::init()?);
+            globals.push(Entry {
+                global: TypeId::of::<G>(),
+                init_count: 0,
+                state: state.clone(),
+            });
+            state
+        }
+    };
+    Ok(state
+        .downcast::<G>()
+        .expect("global state stored under its type id"))
+}
+
+// Parameter tables by plugin type. Kept for the lifetime of the library, so every table and the
+// list of Plugin::params it is built from are only built once.
+static PARAMS: Mutex<Vec<(TypeId, &'static (dyn Any + Send + Sync))>> = Mutex::new(Vec::new());
+
+/// The parameters of `P` with the frei0r parameters they expand to, built on the first call.
+///
+/// Lists [Plugin::PARAMS] if not empty, which is decided at compile time. Otherwise the list of
+/// [Plugin::params], which panics on the first call if it is invalid. Later calls return a
+/// [failed](ParamTable::failed) table instead of building the list again.
+pub(crate) fn params<P: Plugin>() -> &'static ParamTable<P> {
+    let mut tables = PARAMS.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some((_, table)) = tables
+        .iter()
+        .find(|(plugin, _)| *plugin == TypeId::of::<P>())
+    {
+        return table
+            .downcast_ref::<ParamTable<P>>()
+            .expect("parameters stored under the type id of their plugin");
+    }
+    // Built with the lock held, concurrent callers must see the same list.
+    let table = panic::catch_unwind(|| {
+        if !P::PARAMS.is_empty() {
+            return ParamTable::new(P::PARAMS, P::PRESETS);
+        }
+        let params = P::params();
+        ffi::validate_params(params);
+        ffi::validate_presets(params, P::PRESETS);
+        ParamTable::new(params, P::PRESETS)
+    });
+    let (table, payload) = match table {
+        Ok(table) => (table, None),
+        Err(payload) => (ParamTable::failed(), Some(payload)),
+    };
+    let table: &'static ParamTable<P> = Box::leak(Box::new(table));
+    tables.push((TypeId::of::<P>(), table));
+    if let Some(payload) = payload {
+        drop(tables);
+        panic::resume_unwind(payload);
+    }
+    table
+}
@@ -0,0 +1,800 @@
+//! Host side of the frei0r API: load existing frei0r plugin shared libraries and drive them.
+//!
+//! ```no_run
+//! use frei0r_rs2::host::{FrameBuffer, LoadedPlugin};
+//!
+//! let plugin = unsafe { LoadedPlugin::load("/usr/lib/frei0r-1/invert0r.so") }.unwrap();
+//! let mut instance = plugin.construct(64, 64).unwrap();
+//! let inframe = FrameBuffer::new(64 * 64);
+//! let mut outframe = FrameBuffer::new(64 * 64);
+//! instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+//! ```
+
+mod buffer;
+mod registry;
+
+pub use buffer::FrameBuffer;
+pub use registry::{Duplicate, LoadFailure, PluginRegistry, RegistryEntry};
+
+use crate::ffi::{
+    F0R_COLOR_MODEL_BGRA8888, F0R_COLOR_MODEL_PACKED32, F0R_COLOR_MODEL_RGBA8888, F0R_PARAM_BOOL,
+    F0R_PARAM_COLOR, F0R_PARAM_DOUBLE, F0R_PARAM_POSITION, F0R_PARAM_STRING,
+    F0R_PLUGIN_TYPE_FILTER, F0R_PLUGIN_TYPE_MIXER2, F0R_PLUGIN_TYPE_MIXER3, F0R_PLUGIN_TYPE_SOURCE,
+    FREI0R_MAJOR_VERSION, PRESET_EXPLANATION, PRESET_PARAM, f0r_instance_t, f0r_param_bool,
+    f0r_param_color, f0r_param_double, f0r_param_info_t, f0r_param_position, f0r_param_string,
+    f0r_param_t, f0r_plugin_info_t, is_read_only,
+};
+use crate::{Color, ColorModel, ParamValue, Position};
+use libloading::Library;
+use std::{
+    ffi::{CStr, CString, OsStr, c_char, c_int, c_uint},
+    fmt, ptr,
+};
+
+// The function plugin! exports next to the frei0r API, marking plugins built with this crate.
+const MARKER_SYMBOL: &[u8] = b"frei0r_rs2_plugin\0";
+
+// Alignment of frames in bytes required by frei0r.h.
+const FRAME_ALIGNMENT: usize = 16;
+
+/// Errors reported by the host API.
+#[derive(Debug)]
+pub enum HostError {
+    /// The shared library could not be opened or a required symbol is missing.
+    Load(libloading::Error),
+    /// A symbol required for the plugin type is missing.
+    MissingSymbol(&'static str),
+    /// `f0r_init` reported a failure.
+    InitFailed,
+    /// The plugin reported a plugin type unknown to this crate.
+    UnknownPluginType(i32),
+    /// The plugin reported a color model unknown to this crate.
+    UnknownColorModel(i32),
+    /// The plugin was built for a newer frei0r major version.
+    UnsupportedVersion(i32),
+    /// `f0r_construct` returned a null instance.
+    ConstructFailed,
+    /// The parameter index is not in `0..num_params`.
+    ParamIndexOutOfRange(usize),
+    /// The parameter has a different type than the accessor used.
+    ParamTypeMismatch {
+        index: usize,
+        expected: ParamType,
+        actual: ParamType,
+    },
+    /// A frame does not hold exactly `width * height` pixels.
+    FrameSizeMismatch { expected: usize, actual: usize },
+    /// A frame does not start at a 16 byte boundary, as required by `frei0r.h`, see
+    /// [FrameBuffer].
+    MisalignedFrame,
+    /// An input frame required by the plugin type was not supplied.
+    MissingInputFrame(usize),
+    /// The plugin has no parameter with this name.
+    UnknownParam(String),
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostError::Load(err) => write!(f, "failed to load plugin: {err}"),
+            HostError::MissingSymbol(name) => write!(f, "missing symbol {name}"),
+            HostError::InitFailed => write!(f, "plugin initialization failed"),
+            HostError::UnknownPluginType(value) => write!(f, "unknown plugin type {value}"),
+            HostError::UnknownColorModel(value) => write!(f, "unknown color model {value}"),
+            HostError::UnsupportedVersion(value) => {
+                write!(f, "unsupported frei0r version {value}")
+            }
+            HostError::ConstructFailed => write!(f, "plugin failed to construct an instance"),
+            HostError::ParamIndexOutOfRange(index) => {
+                write!(f, "parameter index {index} out of range")
+            }
+            HostError::ParamTypeMismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "parameter {index} has type {actual:?}, accessed as {expected:?}"
+            ),
+            HostError::FrameSizeMismatch { expected, actual } => {
+                write!(f, "frame has {actual} pixels, expected {expected}")
+            }
+            HostError::MisalignedFrame => write!(f, "frame is not aligned to 16 bytes"),
+            HostError::MissingInputFrame(index) => write!(f, "missing input frame {index}"),
+            HostError::UnknownParam(name) => write!(f, "unknown parameter {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for HostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HostError::Load(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<libloading::Error> for HostError {
+    fn from(err: libloading::Error) -> Self {
+        HostError::Load(err)
+    }
+}
+
+/// Runtime equivalent of the [PluginKind](crate::PluginKind) marker types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginType {
+    Source,
+    Filter,
+    Mixer2,
+    Mixer3,
+}
+
+impl PluginType {
+    fn from_raw(value: c_int) -> Result<Self, HostError> {
+        match value as u32 {
+            F0R_PLUGIN_TYPE_SOURCE => Ok(PluginType::Source),
+            F0R_PLUGIN_TYPE_FILTER => Ok(PluginType::Filter),
+            F0R_PLUGIN_TYPE_MIXER2 => Ok(PluginType::Mixer2),
+            F0R_PLUGIN_TYPE_MIXER3 => Ok(PluginType::Mixer3),
+            _ => Err(HostError::UnknownPluginType(value)),
+        }
+    }
+
+    /// Number of input frames the plugin type consumes.
+    pub fn input_count(self) -> usize {
+        match self {
+            PluginType::Source => 0,
+            PluginType::Filter => 1,
+            PluginType::Mixer2 => 2,
+            PluginType::Mixer3 => 3,
+        }
+    }
+}
+
+fn color_model_from_raw(value: c_int) -> Result<ColorModel, HostError> {
+    match value as u32 {
+        F0R_COLOR_MODEL_BGRA8888 => Ok(ColorModel::BGRA8888),
+        F0R_COLOR_MODEL_RGBA8888 => Ok(ColorModel::RGBA8888),
+        F0R_COLOR_MODEL_PACKED32 => Ok(ColorModel::PACKED32),
+        _ => Err(HostError::UnknownColorModel(value)),
+    }
+}
+
+/// Type of a parameter as reported by a loaded plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    Bool,
+    Double,
+    Color,
+    Position,
+    String,
+}
+
+impl ParamType {
+    fn from_raw(value: c_int) -> Option<Self> {
+        match value as u32 {
+            F0R_PARAM_BOOL => Some(ParamType::Bool),
+            F0R_PARAM_DOUBLE => Some(ParamType::Double),
+            F0R_PARAM_COLOR => Some(ParamType::Color),
+            F0R_PARAM_POSITION => Some(ParamType::Position),
+            F0R_PARAM_STRING => Some(ParamType::String),
+            _ => None,
+        }
+    }
+}
+
+/// Plugin information reported by `f0r_get_plugin_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostPluginInfo {
+    /// The (short) name of the plugin
+    pub name: String,
+    /// The plugin author
+    pub author: String,
+    /// The plugin type
+    pub plugin_type: PluginType,
+    /// The color model used
+    pub color_model: ColorModel,
+    /// The frei0r major version the plugin is built for
+    pub frei0r_version: i32,
+    /// The major version of the plugin
+    pub major_version: i32,
+    /// The minor version of the plugin
+    pub minor_version: i32,
+    /// The number of parameters of the plugin
+    pub num_params: usize,
+    /// An optional explanation string
+    pub explanation: Option<String>,
+}
+
+/// Parameter information reported by `f0r_get_param_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostParamInfo {
+    /// The (short) name of the parameter
+    pub name: String,
+    /// The parameter type, `None` if the plugin reported an unknown type
+    pub param_type: Option<ParamType>,
+    /// An optional explanation string
+    pub explanation: Option<String>,
+}
+
+unsafe fn string_from_ptr(ptr: *const c_char) -> Option<String> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+type InitFn = unsafe extern "C" fn() -> c_int;
+type DeinitFn = unsafe extern "C" fn();
+type GetPluginInfoFn = unsafe extern "C" fn(*mut f0r_plugin_info_t);
+type GetParamInfoFn = unsafe extern "C" fn(*mut f0r_param_info_t, c_int);
+type ConstructFn = unsafe extern "C" fn(c_uint, c_uint) -> f0r_instance_t;
+type DestructFn = unsafe extern "C" fn(f0r_instance_t);
+type ParamValueFn = unsafe extern "C" fn(f0r_instance_t, f0r_param_t, c_int);
+type UpdateFn = unsafe extern "C" fn(f0r_instance_t, f64, *const u32, *mut u32);
+type Update2Fn =
+    unsafe extern "C" fn(f0r_instance_t, f64, *const u32, *const u32, *const u32, *mut u32);
+
+// The frei0r entry points of a plugin, resolved from a library or provided in process.
+pub(crate) struct Api {
+    pub(crate) init: InitFn,
+    pub(crate) deinit: DeinitFn,
+    pub(crate) get_plugin_info: GetPluginInfoFn,
+    pub(crate) get_param_info: GetParamInfoFn,
+    pub(crate) construct: ConstructFn,
+    pub(crate) destruct: DestructFn,
+    pub(crate) set_param_value: ParamValueFn,
+    pub(crate) get_param_value: ParamValueFn,
+    pub(crate) update: UpdateFn,
+    pub(crate) update2: Option<Update2Fn>,
+}
+
+/// A frei0r plugin loaded from a shared library.
+///
+/// `f0r_init` is called when the plugin is loaded and `f0r_deinit` when it is dropped.
+pub struct LoadedPlugin {
+    api: Api,
+    info: HostPluginInfo,
+    params: Vec<HostParamInfo>,
+    built_with_crate: bool,
+    // Declared last so the library is unloaded after f0r_deinit has been called.
+    // None for plugins driven in process.
+    _library: Option<Library>,
+}
+
+impl LoadedPlugin {
+    /// Load the plugin at `path`, resolve the `f0r_*` symbols and initialize it.
+    ///
+    /// # Safety
+    ///
+    /// Loading a shared library runs its initialization routines, and the library must
+    /// implement the frei0r API with the signatures declared in `frei0r.h`.
+    pub unsafe fn load(path: impl AsRef<OsStr>) -> Result<Self, HostError> {
+        let library = unsafe { Library::new(path)? };
+        unsafe { Self::from_library(library) }
+    }
+
+    /// Initialize the plugin from an already opened library.
+    ///
+    /// # Safety
+    ///
+    /// The library must implement the frei0r API with the signatures declared in `frei0r.h`.
+    pub unsafe fn from_library(library: Library) -> Result<Self, HostError> {
+        let api = unsafe {
+            Api {
+                init: *library.get(b"f0r_init\0")?,
+                deinit: *library.get(b"f0r_deinit\0")?,
+                get_plugin_info: *library.get(b"f0r_get_plugin_info\0")?,
+                get_param_info: *library.get(b"f0r_get_param_info\0")?,
+                construct: *library.get(b"f0r_construct\0")?,
+                destruct: *library.get(b"f0r_destruct\0")?,
+                set_param_value: *library.get(b"f0r_set_param_value\0")?,
+                get_param_value: *library.get(b"f0r_get_param_value\0")?,
+                update: *library.get(b"f0r_update\0")?,
+                update2: library.get(b"f0r_update2\0").ok().map(|symbol| *symbol),
+            }
+        };
+        let built_with_crate =
+            unsafe { library.get::<unsafe extern "C" fn()>(MARKER_SYMBOL).is_ok() };
+        unsafe { Self::from_api(api, Some(library), built_with_crate) }
+    }
+
+    pub(crate) unsafe fn from_api(
+        api: Api,
+        library: Option<Library>,
+        built_with_crate: bool,
+    ) -> Result<Self, HostError> {
+        if unsafe { (api.init)() } == 0 {
+            return Err(HostError::InitFailed);
+        }
+        // From here on f0r_deinit must be called on every error path.
+        let result = unsafe { Self::query(&api) }.and_then(|(info, params)| {
+            match (info.plugin_type, api.update2) {
+                // Mixers are required to export f0r_update2.
+                (PluginType::Mixer2 | PluginType::Mixer3, None) => {
+                    Err(HostError::MissingSymbol("f0r_update2"))
+                }
+                _ => Ok((info, params)),
+            }
+        });
+        match result {
+            Ok((info, params)) => Ok(LoadedPlugin {
+                api,
+                info,
+                params,
+                built_with_crate,
+                _library: library,
+            }),
+            Err(err) => {
+                unsafe { (api.deinit)() };
+                Err(err)
+            }
+        }
+    }
+
+    unsafe fn query(api: &Api) -> Result<(HostPluginInfo, Vec<HostParamInfo>), HostError> {
+        let mut raw = f0r_plugin_info_t {
+            name: ptr::null(),
+            author: ptr::null(),
+            plugin_type: 0,
+            color_model: 0,
+            frei0r_version: 0,
+            major_version: 0,
+            minor_version: 0,
+            num_params: 0,
+            explanation: ptr::null(),
+        };
+        unsafe { (api.get_plugin_info)(&mut raw) };
+
+        if raw.frei0r_version > FREI0R_MAJOR_VERSION as i32 {
+            return Err(HostError::UnsupportedVersion(raw.frei0r_version));
+        }
+        let info = HostPluginInfo {
+            name: unsafe { string_from_ptr(raw.name) }.unwrap_or_default(),
+            author: unsafe { string_from_ptr(raw.author) }.unwrap_or_default(),
+            plugin_type: PluginType::from_raw(raw.plugin_type)?,
+            color_model: color_model_from_raw(raw.color_model)?,
+            frei0r_version: raw.frei0r_version,
+            major_version: raw.major_version,
+            minor_version: raw.minor_version,
+            num_params: raw.num_params.max(0) as usize,
+            explanation: unsafe { string_from_ptr(raw.explanation) },
+        };
+
+        let params = (0..info.num_params)
+            .map(|index| {
+                let mut raw = f0r_param_info_t {
+                    name: ptr::null(),
+                    type_: -1,
+                    explanation: ptr::null(),
+                };
+                unsafe { (api.get_param_info)(&mut raw, index as c_int) };
+                HostParamInfo {
+                    name: unsafe { string_from_ptr(raw.name) }.unwrap_or_default(),
+                    param_type: ParamType::from_raw(raw.type_),
+                    explanation: unsafe { string_from_ptr(raw.explanation) },
+                }
+            })
+            .collect();
+
+        Ok((info, params))
+    }
+
+    /// Information about the plugin.
+    pub fn info(&self) -> &HostPluginInfo {
+        &self.info
+    }
+
+    /// Information about all plugin parameters, in index order.
+    pub fn params(&self) -> &[HostParamInfo] {
+        &self.params
+    }
+
+    /// Information about the parameter at `index`.
+    pub fn param_info(&self, index: usize) -> Option<&HostParamInfo> {
+        self.params.get(index)
+    }
+
+    /// The index of the parameter called `name`.
+    pub fn param_index(&self, name: &str) -> Option<usize> {
+        self.params.iter().position(|param| param.name == name)
+    }
+
+    /// Whether the plugin was exported with [plugin!](crate::plugin).
+    ///
+    /// Output and preset parameters are only recognized in plugins built with this crate, see
+    /// [LoadedPlugin::presets] and [HostInstance::snapshot].
+    pub fn built_with_crate(&self) -> bool {
+        self.built_with_crate
+    }
+
+    /// The names of the presets of a plugin built with this crate, see
+    /// [Plugin::PRESETS](crate::Plugin::PRESETS). Empty for other plugins.
+    pub fn presets(&self) -> Vec<&str> {
+        self.preset_param()
+            .and_then(|index| self.params[index].explanation.as_deref())
+            .and_then(|explanation| {
+                let names = explanation.strip_prefix(PRESET_EXPLANATION.to_str().ok()?)?;
+                names.strip_prefix(' ')
+            })
+            .map(|names| names.split(", ").collect())
+            .unwrap_or_default()
+    }
+
+    /// The index of the parameter plugins built with this crate apply presets with.
+    fn preset_index(&self) -> Option<usize> {
+        // A plugin without presets may have an ordinary parameter named preset.
+        self.preset_param().filter(|_| !self.presets().is_empty())
+    }
+
+    /// The index of the parameter named like the preset parameter in a plugin built with this
+    /// crate.
+    fn preset_param(&self) -> Option<usize> {
+        if !self.built_with_crate {
+            return None;
+        }
+        self.params
+            .iter()
+            .position(|param| param.name.as_bytes() == PRESET_PARAM.to_bytes())
+    }
+
+    /// Whether a parameter is an output of a plugin built with this crate, see
+    /// [ParamInfo::as_output](crate::ParamInfo::as_output).
+    fn is_output(&self, param: &HostParamInfo) -> bool {
+        let Some(explanation) = param.explanation.as_deref() else {
+            return false;
+        };
+        self.built_with_crate && is_read_only(explanation.as_bytes())
+    }
+
+    /// Construct an instance processing frames of the given resolution.
+    pub fn construct(&self, width: u32, height: u32) -> Result<HostInstance<'_>, HostError> {
+        let instance = unsafe { (self.api.construct)(width, height) };
+        if instance.is_null() {
+            return Err(HostError::ConstructFailed);
+        }
+        Ok(HostInstance {
+            plugin: self,
+            instance,
+            width,
+            height,
+        })
+    }
+}
+
+impl Drop for LoadedPlugin {
+    fn drop(&mut self) {
+        unsafe { (self.api.deinit)() };
+    }
+}
+
+impl fmt::Debug for LoadedPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadedPlugin")
+            .field("info", &self.info)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An effect instance of a [LoadedPlugin], destructed when dropped.
+pub struct HostInstance<'a> {
+    plugin: &'a LoadedPlugin,
+    instance: f0r_instance_t,
+    width: u32,
+    height: u32,
+}
+
+impl<'a> HostInstance<'a> {
+    /// The plugin this instance was constructed from.
+    pub fn plugin(&self) -> &'a LoadedPlugin {
+        self.plugin
+    }
+
+    /// Width of the processed frames.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height of the processed frames.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of pixels in a frame.
+    pub fn frame_length(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    fn check_param(&self, index: usize, expected: ParamType) -> Result<c_int, HostError> {
+        let info = self
+            .plugin
+            .param_info(index)
+            .ok_or(HostError::ParamIndexOutOfRange(index))?;
+        match info.param_type {
+            Some(actual) if actual == expected => Ok(index as c_int),
+            Some(actual) => Err(HostError::ParamTypeMismatch {
+                index,
+                expected,
+                actual,
+            }),
+            None => Err(HostError::ParamIndexOutOfRange(index)),
+        }
+    }
+
+    unsafe fn set_raw<T>(
+        &mut self,
+        index: usize,
+        expected: ParamType,
+        value: &mut T,
+    ) -> Result<(), HostError> {
+        let index = self.check_param(index, expected)?;
+        unsafe {
+            (self.plugin.api.set_param_value)(self.instance, value as *mut T as f0r_param_t, index)
+        };
+        Ok(())
+    }
+
+    unsafe fn get_raw<T>(
+        &self,
+        index: usize,
+        expected: ParamType,
+        value: &mut T,
+    ) -> Result<(), HostError> {
+        let index = self.check_param(index, expected)?;
+        unsafe {
+            (self.plugin.api.get_param_value)(self.instance, value as *mut T as f0r_param_t, index)
+        };
+        Ok(())
+    }
+
+    /// Set a bool parameter.
+    pub fn set_bool(&mut self, index: usize, value: bool) -> Result<(), HostError> {
+        let mut value: f0r_param_bool = if value { 1.0 } else { 0.0 };
+        unsafe { self.set_raw(index, ParamType::Bool, &mut value) }
+    }
+
+    /// Set a double parameter.
+    pub fn set_double(&mut self, index: usize, value: f64) -> Result<(), HostError> {
+        let mut value: f0r_param_double = value;
+        unsafe { self.set_raw(index, ParamType::Double, &mut value) }
+    }
+
+    /// Set a color parameter.
+    pub fn set_color(&mut self, index: usize, value: Color) -> Result<(), HostError> {
+        let mut value = f0r_param_color {
+            r: value.r,
+            g: value.g,
+            b: value.b,
+        };
+        unsafe { self.set_raw(index, ParamType::Color, &mut value) }
+    }
+
+    /// Set a position parameter.
+    pub fn set_position(&mut self, index: usize, value: Position) -> Result<(), HostError> {
+        let mut value = f0r_param_position {
+            x: value.x,
+            y: value.y,
+        };
+        unsafe { self.set_raw(index, ParamType::Position, &mut value) }
+    }
+
+    /// Set a string parameter. The plugin copies the string.
+    pub fn set_string(&mut self, index: usize, value: &CStr) -> Result<(), HostError> {
+        let mut value = value.as_ptr() as f0r_param_string;
+        unsafe { self.set_raw(index, ParamType::String, &mut value) }
+    }
+
+    /// Apply a preset of a plugin built with this crate by name, see [LoadedPlugin::presets].
+    pub fn apply_preset(&mut self, name: &CStr) -> Result<(), HostError> {
+        let index = self
+            .plugin
+            .preset_index()
+            .ok_or_else(|| HostError::UnknownParam(PRESET_PARAM.to_string_lossy().into_owned()))?;
+        self.set_string(index, name)
+    }
+
+    /// Get a bool parameter.
+    pub fn get_bool(&self, index: usize) -> Result<bool, HostError> {
+        let mut value: f0r_param_bool = 0.0;
+        unsafe { self.get_raw(index, ParamType::Bool, &mut value)? };
+        Ok(value >= 0.5)
+    }
+
+    /// Get a double parameter.
+    pub fn get_double(&self, index: usize) -> Result<f64, HostError> {
+        let mut value: f0r_param_double = 0.0;
+        unsafe { self.get_raw(index, ParamType::Double, &mut value)? };
+        Ok(value)
+    }
+
+    /// Get a color parameter.
+    pub fn get_color(&self, index: usize) -> Result<Color, HostError> {
+        let mut value = f0r_param_color {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+        };
+        unsafe { self.get_raw(index, ParamType::Color, &mut value)? };
+        Ok(Color {
+            r: value.r,
+            g: value.g,
+            b: value.b,
+        })
+    }
+
+    /// Get a position parameter.
+    pub fn get_position(&self, index: usize) -> Result<Position, HostError> {
+        let mut value = f0r_param_position { x: 0.0, y: 0.0 };
+        unsafe { self.get_raw(index, ParamType::Position, &mut value)? };
+        Ok(Position {
+            x: value.x,
+            y: value.y,
+        })
+    }
+
+    /// Get a string parameter. The string is copied out of the plugin, `None` if the plugin
+    /// returned a null pointer.
+    pub fn get_string(&self, index: usize) -> Result<Option<String>, HostError> {
+        let mut value: f0r_param_string = ptr::null_mut();
+        unsafe { self.get_raw(index, ParamType::String, &mut value)? };
+        Ok(unsafe { string_from_ptr(value) })
+    }
+
+    /// The values of all parameters by name, without the output and preset parameters of
+    /// plugins built with this crate. String parameters the plugin returns null for are left out.
+    pub fn snapshot(&self) -> Result<Vec<(String, ParamValue)>, HostError> {
+        let preset_index = self.plugin.preset_index();
+        let mut snapshot = Vec::new();
+        for (index, param) in self.plugin.params.iter().enumerate() {
+            if self.plugin.is_output(param) || preset_index == Some(index) {
+                continue;
+            }
+            let value = match param.param_type {
+                Some(ParamType::Bool) => ParamValue::Bool(self.get_bool(index)?),
+                Some(ParamType::Double) => ParamValue::Double(self.get_double(index)?),
+                Some(ParamType::Color) => ParamValue::Color(self.get_color(index)?),
+                Some(ParamType::Position) => ParamValue::Position(self.get_position(index)?),
+                Some(ParamType::String) => match self.get_string(index)? {
+                    Some(value) => ParamValue::String(value),
+                    None => continue,
+                },
+                None => continue,
+            };
+            snapshot.push((param.name.clone(), value));
+        }
+        Ok(snapshot)
+    }
+
+    /// Set the parameters of a [snapshot](Self::snapshot) by name.
+    ///
+    /// Names the plugin does not have and values of a different type are skipped, so snapshots
+    /// of other versions of the plugin can be restored. Parameters missing from the snapshot keep
+    /// their value.
+    pub fn restore(&mut self, snapshot: &[(String, ParamValue)]) -> Result<(), HostError> {
+        for (name, value) in snapshot {
+            let Some(index) = self.plugin.param_index(name) else {
+                continue;
+            };
+            let result = match value {
+                ParamValue::Bool(value) => self.set_bool(index, *value),
+                ParamValue::Double(value) => self.set_double(index, *value),
+                ParamValue::Color(value) => self.set_color(index, *value),
+                ParamValue::Position(value) => self.set_position(index, *value),
+                ParamValue::String(value) => match CString::new(value.as_str()) {
+                    Ok(value) => self.set_string(index, &value),
+                    Err(_) => continue,
+                },
+            };
+            match result {
+                Err(HostError::ParamTypeMismatch { .. }) => continue,
+                result => result?,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_frame(&self, frame: &[u32]) -> Result<(), HostError> {
+        let expected = self.frame_length();
+        if frame.len() != expected {
+            return Err(HostError::FrameSizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+        // Plugins may use aligned SIMD loads and stores.
+        if !frame.as_ptr().addr().is_multiple_of(FRAME_ALIGNMENT) {
+            return Err(HostError::MisalignedFrame);
+        }
+        Ok(())
+    }
+
+    fn frame_ptr(&self, frame: Option<&[u32]>, index: usize) -> Result<*const u32, HostError> {
+        match frame {
+            Some(frame) => {
+                self.check_frame(frame)?;
+                Ok(frame.as_ptr())
+            }
+            None if index < self.plugin.info.plugin_type.input_count() => {
+                Err(HostError::MissingInputFrame(index))
+            }
+            None => Ok(ptr::null()),
+        }
+    }
+
+    /// Process a frame using `f0r_update`.
+    ///
+    /// `inframe` is required for filters and ignored for sources. Mixers must use [Self::update2].
+    /// Frames must start at a 16 byte boundary, e.g. by using [FrameBuffer].
+    pub fn update(
+        &mut self,
+        time: f64,
+        inframe: Option<&[u32]>,
+        outframe: &mut [u32],
+    ) -> Result<(), HostError> {
+        match self.plugin.info.plugin_type {
+            PluginType::Source | PluginType::Filter => {}
+            PluginType::Mixer2 | PluginType::Mixer3 => return Err(HostError::MissingInputFrame(1)),
+        }
+        let inframe = self.frame_ptr(inframe, 0)?;
+        self.check_frame(outframe)?;
+        unsafe { (self.plugin.api.update)(self.instance, time, inframe, outframe.as_mut_ptr()) };
+        Ok(())
+    }
+
+    /// Process frames using `f0r_update2`, falling back to `f0r_update` for sources and filters
+    /// that do not export it. Frames must start at a 16 byte boundary, e.g. by using
+    /// [FrameBuffer].
+    pub fn update2(
+        &mut self,
+        time: f64,
+        inframe1: Option<&[u32]>,
+        inframe2: Option<&[u32]>,
+        inframe3: Option<&[u32]>,
+        outframe: &mut [u32],
+    ) -> Result<(), HostError> {
+        let inframe1 = self.frame_ptr(inframe1, 0)?;
+        let inframe2 = self.frame_ptr(inframe2, 1)?;
+        let inframe3 = self.frame_ptr(inframe3, 2)?;
+        self.check_frame(outframe)?;
+        match self.plugin.api.update2 {
+            Some(update2) => unsafe {
+                update2(
+                    self.instance,
+                    time,
+                    inframe1,
+                    inframe2,
+                    inframe3,
+                    outframe.as_mut_ptr(),
+                )
+            },
+            // Only sources and filters can be loaded without f0r_update2.
+            None => unsafe {
+                (self.plugin.api.update)(self.instance, time, inframe1, outframe.as_mut_ptr())
+            },
+        }
+        Ok(())
+    }
+}
+
+impl Drop for HostInstance<'_> {
+    fn drop(&mut self) {
+        unsafe { (self.plugin.api.destruct)(self.instance) };
+    }
+}
+
+impl fmt::Debug for HostInstance<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostInstance")
+            .field("plugin", &self.plugin.info.name)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,85 @@
+//! Frame storage meeting the alignment frei0r requires.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+// Sixteen bytes of pixels, the alignment of frames required by frei0r.h.
+#[derive(Clone, Copy, Default)]
+#[repr(C, align(16))]
+struct Block([u32; 4]);
+
+const BLOCK_PIXELS: usize = size_of::<Block>() / size_of::<u32>();
+
+/// A frame of pixels starting at a 16 byte boundary, as
+/// [HostInstance::update](super::HostInstance::update) requires. `Vec<u32>` only guarantees the
+/// alignment of `u32`.
+///
+/// Dereferences to the pixels.
+///
+/// ```
+/// use frei0r_rs2::host::FrameBuffer;
+///
+/// let mut frame = FrameBuffer::new(64 * 64);
+/// frame.fill(u32::MAX);
+/// assert_eq!(frame.len(), 64 * 64);
+/// assert!(frame.as_ptr().addr().is_multiple_of(16));
+/// ```
+#[derive(Clone, Default)]
+pub struct FrameBuffer {
+    blocks: Vec<Block>,
+    len: usize,
+}
+
+impl FrameBuffer {
+    /// A frame of `len` pixels set to 0.
+    pub fn new(len: usize) -> Self {
+        FrameBuffer {
+            blocks: vec![Block::default(); len.div_ceil(BLOCK_PIXELS)],
+            len,
+        }
+    }
+
+    /// A frame holding a copy of `pixels`.
+    pub fn from_slice(pixels: &[u32]) -> Self {
+        let mut frame = FrameBuffer::new(pixels.len());
+        frame.copy_from_slice(pixels);
+        frame
+    }
+}
+
+impl Deref for FrameBuffer {
+    type Target = [u32];
+
+    fn deref(&self) -> &[u32] {
+        // Block is a repr(C) array of u32 without padding, the blocks hold at least len pixels.
+        unsafe { std::slice::from_raw_parts(self.blocks.as_ptr().cast(), self.len) }
+    }
+}
+
+impl DerefMut for FrameBuffer {
+    fn deref_mut(&mut self) -> &mut [u32] {
+        unsafe { std::slice::from_raw_parts_mut(self.blocks.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+impl FromIterator<u32> for FrameBuffer {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        FrameBuffer::from_slice(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl PartialEq for FrameBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for FrameBuffer {}
+
+impl fmt::Debug for FrameBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,215 @@
+use super::{HostError, HostParamInfo, HostPluginInfo, LoadedPlugin, PluginType};
+use crate::ColorModel;
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A plugin discovered by a [PluginRegistry].
+#[derive(Debug, Clone)]
+pub struct RegistryEntry {
+    /// Path of the shared library
+    pub path: PathBuf,
+    /// Information reported by the plugin
+    pub info: HostPluginInfo,
+    /// Parameters reported by the plugin
+    pub params: Vec<HostParamInfo>,
+}
+
+impl RegistryEntry {
+    /// File name of the library without its extension, the name ffmpeg's `filter_name=` uses.
+    pub fn file_stem(&self) -> Option<&str> {
+        self.path.file_stem().and_then(OsStr::to_str)
+    }
+
+    /// Load the plugin described by this entry.
+    ///
+    /// # Safety
+    ///
+    /// See [LoadedPlugin::load].
+    pub unsafe fn load(&self) -> Result<LoadedPlugin, HostError> {
+        unsafe { LoadedPlugin::load(&self.path) }
+    }
+}
+
+/// A library that was found while scanning but could not be loaded.
+#[derive(Debug)]
+pub struct LoadFailure {
+    pub path: PathBuf,
+    pub error: HostError,
+}
+
+/// A plugin whose name was already registered by a library with higher precedence.
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    /// The plugin name
+    pub name: String,
+    /// The library that was kept
+    pub kept: PathBuf,
+    /// The library that was ignored
+    pub shadowed: PathBuf,
+}
+
+/// Index of frei0r plugins found on disk.
+///
+/// Libraries are scanned in the order they are added and the first library registering a plugin
+/// name takes precedence, later ones are reported in [Self::duplicates].
+/// Libraries are only loaded while scanning, use [RegistryEntry::load] to load one again.
+#[derive(Debug, Default)]
+pub struct PluginRegistry {
+    entries: Vec<RegistryEntry>,
+    by_name: HashMap<String, usize>,
+    duplicates: Vec<Duplicate>,
+    failures: Vec<LoadFailure>,
+}
+
+impl PluginRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The directories searched for plugins, in order of precedence.
+    ///
+    /// If `FREI0R_PATH` is set its entries replace the default list, otherwise
+    /// `$HOME/.frei0r-1/lib`, `/usr/local/lib/frei0r-1` and `/usr/lib/frei0r-1` are used.
+    pub fn search_paths() -> Vec<PathBuf> {
+        if let Some(path) = env::var_os("FREI0R_PATH") {
+            return env::split_paths(&path)
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .collect();
+        }
+        let mut paths = Vec::new();
+        if let Some(home) = env::home_dir() {
+            paths.push(home.join(".frei0r-1").join("lib"));
+        }
+        paths.push(PathBuf::from("/usr/local/lib/frei0r-1"));
+        paths.push(PathBuf::from("/usr/lib/frei0r-1"));
+        paths
+    }
+
+    /// Create a registry by scanning [Self::search_paths].
+    ///
+    /// # Safety
+    ///
+    /// Every library found is loaded, see [LoadedPlugin::load].
+    pub unsafe fn discover() -> Self {
+        let mut registry = Self::new();
+        for dir in Self::search_paths() {
+            // Missing standard directories are expected.
+            let _ = unsafe { registry.scan_dir(&dir) };
+        }
+        registry
+    }
+
+    /// Scan `dir` and its vendor subdirectories for plugin libraries.
+    ///
+    /// `frei0r.h` allows a single level of vendor directories, deeper directories and symbolic
+    /// links to directories are skipped. Returns an error only if `dir` itself cannot be read,
+    /// libraries that fail to load are recorded in [Self::failures].
+    ///
+    /// # Safety
+    ///
+    /// Every library found is loaded, see [LoadedPlugin::load].
+    pub unsafe fn scan_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        unsafe { self.scan_dir_level(dir.as_ref(), true) }
+    }
+
+    unsafe fn scan_dir_level(&mut self, dir: &Path, vendors: bool) -> io::Result<()> {
+        let mut entries = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Some((entry.path(), entry.file_type().ok()?)))
+            .collect::<Vec<_>>();
+        // Loading order inside a directory is unspecified, sort for reproducible precedence.
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (path, file_type) in entries {
+            // The file type of a directory entry does not follow symbolic links.
+            if file_type.is_dir() {
+                if vendors {
+                    let _ = unsafe { self.scan_dir_level(&path, false) };
+                }
+            } else if path.extension() == Some(OsStr::new(env::consts::DLL_EXTENSION))
+                && path.is_file()
+            {
+                unsafe { self.scan_file(path) };
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the library at `path` and register the plugin it contains.
+    ///
+    /// # Safety
+    ///
+    /// See [LoadedPlugin::load].
+    pub unsafe fn scan_file(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        match unsafe { LoadedPlugin::load(&path) } {
+            Ok(plugin) => self.insert(RegistryEntry {
+                path,
+                info: plugin.info().clone(),
+                params: plugin.params().to_vec(),
+            }),
+            Err(error) => self.failures.push(LoadFailure { path, error }),
+        }
+    }
+
+    fn insert(&mut self, entry: RegistryEntry) {
+        if let Some(&index) = self.by_name.get(&entry.info.name) {
+            self.duplicates.push(Duplicate {
+                name: entry.info.name,
+                kept: self.entries[index].path.clone(),
+                shadowed: entry.path,
+            });
+        } else {
+            self.by_name
+                .insert(entry.info.name.clone(), self.entries.len());
+            self.entries.push(entry);
+        }
+    }
+
+    /// All registered plugins, in scan order.
+    pub fn entries(&self) -> &[RegistryEntry] {
+        &self.entries
+    }
+
+    /// Look up a plugin by the name it reports in its plugin info.
+    pub fn get(&self, name: &str) -> Option<&RegistryEntry> {
+        self.by_name.get(name).map(|&index| &self.entries[index])
+    }
+
+    /// Look up a plugin by library file name without extension (e.g. `invert0r`), the way
+    /// ffmpeg's `filter_name=` option does.
+    pub fn find_file(&self, stem: &str) -> Option<&RegistryEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.file_stem() == Some(stem))
+    }
+
+    /// All plugins of the given type.
+    pub fn by_type(&self, plugin_type: PluginType) -> impl Iterator<Item = &RegistryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.info.plugin_type == plugin_type)
+    }
+
+    /// All plugins using the given color model.
+    pub fn by_color_model(&self, color_model: ColorModel) -> impl Iterator<Item = &RegistryEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.info.color_model == color_model)
+    }
+
+    /// Plugins that were ignored because a plugin with the same name was registered first.
+    pub fn duplicates(&self) -> &[Duplicate] {
+        &self.duplicates
+    }
+
+    /// Libraries that failed to load.
+    pub fn failures(&self) -> &[LoadFailure] {
+        &self.failures
+    }
+}
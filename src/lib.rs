@@ -2,31 +2,61 @@
 //!
 //! See examples for API usage.
 
+mod diagnostic;
 #[doc(hidden)]
 pub mod ffi;
+mod frame;
+mod global;
+#[cfg(feature = "host")]
+pub mod host;
 mod param;
+mod pixel;
+mod preset;
+mod resource;
+mod smoothing;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod value;
+pub use diagnostic::{Diagnostic, set_diagnostic_hook};
 pub use ffi::{KindFilter, KindMixer2, KindMixer3, KindSource, PluginKind};
-pub use param::{Color, ParamInfo, ParamKind, Position};
+pub use frame::{Frame, FrameMut};
+#[cfg(feature = "derive")]
+pub use frei0r_derive2::Frei0rParams;
+pub use param::{
+    Choice, ChoiceEncoding, ChoiceParam, Color, Composite, CompositeParam, Frei0rParams,
+    ParamDefault, ParamInfo, ParamKind, ParamSet, Position, Range,
+};
+pub use pixel::{Bgra8, Packed32, Pixel, Rgba8};
+pub use preset::{Preset, PresetValue};
+pub use resource::{PathValue, Resource, ResourcePath};
+pub use smoothing::Smoothing;
 use std::{
-    ffi::CStr,
-    sync::{LockResult, Mutex, MutexGuard},
+    ffi::{CStr, c_int, c_uint},
+    sync::{
+        Arc, LockResult, Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
 };
+pub use value::ParamValue;
 
 use crate::ffi::{Instance, PluginKindUpdate};
 
 /// Convert mutable u32 slice to mutable u8 slice
+#[deprecated(note = "use FrameMut::as_bytes_mut or Pixel::slice_as_bytes_mut")]
 pub fn slice_to_bytes_mut(slice: &mut [u32]) -> &mut [u8] {
     unsafe { std::slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<u8>(), size_of_val(slice)) }
 }
 
 /// Convert u32 slice to u8 slice
+#[deprecated(note = "use Frame::as_bytes or Pixel::slice_as_bytes")]
 pub fn slice_to_bytes(slice: &[u32]) -> &[u8] {
     unsafe { std::slice::from_raw_parts(slice.as_ptr().cast::<u8>(), size_of_val(slice)) }
 }
 
 /// The plugin base trait. Plugins must also implement one of the
 /// [SourcePlugin], [FilterPlugin], [Mixer2Plugin] or [Mixer3Plugin] traits
-/// corresponding to the [PluginKind] associated type.
+/// corresponding to the [PluginKind] associated type, or its frame based counterpart such as
+/// [FilterFramePlugin].
 ///
 /// The update functions are where the core effect processing happens. The application calls it after it has
 /// set the necessary parameter values.
@@ -36,32 +66,206 @@ pub fn slice_to_bytes(slice: &[u32]) -> &[u8] {
 pub trait Plugin: 'static + Sized + Send {
     type Kind: PluginKind;
 
+    /// The pixel layout of the frames, which determines the [ColorModel] reported to the
+    /// application.
+    type Pixel: Pixel;
+
     /// The list of plugin parameters
-    const PARAMS: &'static [ParamInfo<Self>];
+    ///
+    /// Leave empty and implement [Plugin::params] instead if the list is only known at runtime.
+    const PARAMS: &'static [ParamInfo<Self>] = &[];
+
+    /// Named sets of parameter values, e.g. looks offered by a color grading plugin.
+    ///
+    /// If not empty, the application sees an extra string parameter named `preset` after the
+    /// others, with the preset names listed in its explanation. Setting it to a name sets the
+    /// values of that preset as if the application set them one by one, reading it returns the
+    /// name of the preset applied last.
+    ///
+    /// ```
+    /// # use frei0r_rs2::{Preset, PresetValue};
+    /// const PRESETS: &[Preset] = &[
+    ///     Preset {
+    ///         name: c"warm",
+    ///         values: &[(c"temperature", PresetValue::Double(0.8))],
+    ///     },
+    ///     Preset {
+    ///         name: c"cold",
+    ///         values: &[(c"temperature", PresetValue::Double(0.2))],
+    ///     },
+    /// ];
+    /// ```
+    const PRESETS: &'static [Preset] = &[];
+
+    /// Build the list of plugin parameters at runtime, e.g. from a configuration file installed
+    /// next to the plugin.
+    ///
+    /// Only called if [Plugin::PARAMS] is empty, once for the lifetime of the library, by
+    /// `f0r_init` or the first call needing the list. Leak the list to return it, e.g. with
+    /// [Vec::leak]. The checks [plugin!] applies to [Plugin::PARAMS] at compile time are applied
+    /// to the list when it is built, panicking if it is invalid. The panic is reported once, after
+    /// which `f0r_init` and `f0r_construct` fail and the plugin reports no parameters.
+    fn params() -> &'static [ParamInfo<Self>] {
+        Self::PARAMS
+    }
 
     /// Called by the application to query plugin information.
     fn info() -> PluginInfo;
 
     /// Constructor for effect instances.
     ///
-    /// The resolution must be an integer multiple of 8, must be greater than 0 and be at most 2048
-    /// in both dimensions.
+    /// The resolution is an integer multiple of 8, greater than 0 and at most 2048 in both
+    /// dimensions, other resolutions are rejected before this is called.
     ///
     /// The plugin must set default values for all parameters in this function.
     fn new(width: usize, height: usize) -> Self;
+
+    /// Fallible constructor for effect instances, defaults to [Plugin::new].
+    ///
+    /// Override it to refuse a resolution or report a failure to acquire resources. Returning an
+    /// error makes `f0r_construct` return a null instance to the application.
+    fn try_new(width: usize, height: usize) -> Result<Self, BoxError> {
+        Ok(Self::new(width, height))
+    }
+
+    /// Called once before an update if parameters were set since the previous one, with their
+    /// indices in the parameter list.
+    ///
+    /// Recompute state derived from parameters here rather than in the setters, which the
+    /// application may call for every parameter before every frame. Every parameter is reported
+    /// before the first update. Values ignored by the parameter checks do not count as set.
+    fn params_changed(&mut self, changed: &ParamSet) {
+        let _ = changed;
+    }
+}
+
+/// Library wide state of a plugin, such as lookup tables or fonts that are loaded once and then
+/// shared read-only by all instances.
+///
+/// Plugins opt in by exporting themselves with `plugin!(MyPlugin, global = MyState)`. The state
+/// is then created by `f0r_init` when the application loads the plugin, and if initialization
+/// fails `f0r_init` reports failure to the application. Instances get it with
+/// [PluginGlobal::get], typically in [Plugin::try_new].
+///
+/// ```
+/// # use frei0r_rs2::*;
+/// # use std::sync::Arc;
+/// struct Gamma([u8; 256]);
+///
+/// impl PluginGlobal for Gamma {
+///     fn init() -> Result<Self, BoxError> {
+///         Ok(Gamma(std::array::from_fn(|i| ((i as f64 / 255.0).powf(2.2) * 255.0) as u8)))
+///     }
+/// }
+///
+/// struct GammaPlugin {
+///     table: Arc<Gamma>,
+/// }
+///
+/// impl Plugin for GammaPlugin {
+///     type Kind = KindFilter;
+///     type Pixel = Rgba8;
+/// #   fn info() -> PluginInfo {
+/// #       plugin_info!(name: c"gamma")
+/// #   }
+///
+///     fn new(width: usize, height: usize) -> Self {
+///         Self::try_new(width, height).expect("gamma table")
+///     }
+///
+///     fn try_new(_width: usize, _height: usize) -> Result<Self, BoxError> {
+///         Ok(GammaPlugin { table: Gamma::get()? })
+///     }
+/// }
+/// # impl FilterPlugin for GammaPlugin {
+/// #     fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+/// #         outframe.copy_from_slice(inframe);
+/// #     }
+/// # }
+///
+/// plugin!(GammaPlugin, global = Gamma);
+/// # fn main() {}
+/// ```
+pub trait PluginGlobal: 'static + Sized + Send + Sync {
+    /// Create the state.
+    fn init() -> Result<Self, BoxError>;
+
+    /// Release the state, called by `f0r_deinit`.
+    fn deinit(self) {}
+
+    /// The state created by `f0r_init`, clone it to keep it for the lifetime of an instance.
+    ///
+    /// Created on demand if the application did not call `f0r_init`, returning the error of
+    /// [PluginGlobal::init] if that fails.
+    fn get() -> Result<Arc<Self>, BoxError> {
+        global::get::<Self>()
+    }
+}
+
+impl PluginGlobal for () {
+    fn init() -> Result<Self, BoxError> {
+        Ok(())
+    }
+}
+
+/// Error type returned by fallible plugin callbacks.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Error returned when an application requests a resolution not allowed by the frei0r spec.
+///
+/// Width and height must be integer multiples of 8, greater than 0 and at most 2048.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionError {
+    pub width: u32,
+    pub height: u32,
 }
 
+impl DimensionError {
+    const MAX_DIMENSION: u32 = 2048;
+
+    pub(crate) fn check(width: u32, height: u32) -> Result<(), Self> {
+        let valid = |dimension: u32| {
+            dimension > 0 && dimension.is_multiple_of(8) && dimension <= Self::MAX_DIMENSION
+        };
+        if valid(width) && valid(height) {
+            Ok(())
+        } else {
+            Err(DimensionError { width, height })
+        }
+    }
+}
+
+impl std::fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid resolution {}x{}, dimensions must be positive multiples of 8 of at most {}",
+            self.width,
+            self.height,
+            Self::MAX_DIMENSION
+        )
+    }
+}
+
+impl std::error::Error for DimensionError {}
+
 /// A source plugin, must be implemented if Plugin::Kind = KindSource
+///
+/// Implement [SourceFramePlugin] instead to receive typed [FrameMut] views.
 pub trait SourcePlugin: Plugin<Kind = KindSource> {
     fn update_source(&mut self, time: f64, outframe: &mut [u32]);
 }
 
 /// A filter plugin, must be implemented if Plugin::Kind = KindFilter
+///
+/// Implement [FilterFramePlugin] instead to receive typed [Frame] views.
 pub trait FilterPlugin: Plugin<Kind = KindFilter> {
     fn update_filter(&mut self, time: f64, inframe: &[u32], outframe: &mut [u32]);
 }
 
 /// A mixer2 plugin, must be implemented if Plugin::Kind = KindMixer2
+///
+/// Implement [Mixer2FramePlugin] instead to receive typed [Frame] views.
 pub trait Mixer2Plugin: Plugin<Kind = KindMixer2> {
     fn update_mixer2(
         &mut self,
@@ -73,6 +277,8 @@ pub trait Mixer2Plugin: Plugin<Kind = KindMixer2> {
 }
 
 /// A mixer3 plugin, must be implemented if Plugin::Kind = KindMixer3
+///
+/// Implement [Mixer3FramePlugin] instead to receive typed [Frame] views.
 pub trait Mixer3Plugin: Plugin<Kind = KindMixer3> {
     fn update_mixer3(
         &mut self,
@@ -84,16 +290,111 @@ pub trait Mixer3Plugin: Plugin<Kind = KindMixer3> {
     );
 }
 
+/// A source plugin working on frames of its [Plugin::Pixel] type, the alternative to
+/// [SourcePlugin]. Implemented for every [SourcePlugin].
+pub trait SourceFramePlugin: Plugin<Kind = KindSource> {
+    fn update_source_frame(&mut self, time: f64, outframe: FrameMut<'_, Self::Pixel>);
+}
+
+impl<T: SourcePlugin> SourceFramePlugin for T {
+    fn update_source_frame(&mut self, time: f64, outframe: FrameMut<'_, Self::Pixel>) {
+        self.update_source(time, outframe.into_u32_slice());
+    }
+}
+
+/// A filter plugin working on frames of its [Plugin::Pixel] type, the alternative to
+/// [FilterPlugin]. Implemented for every [FilterPlugin].
+pub trait FilterFramePlugin: Plugin<Kind = KindFilter> {
+    fn update_filter_frame(
+        &mut self,
+        time: f64,
+        inframe: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    );
+}
+
+impl<T: FilterPlugin> FilterFramePlugin for T {
+    fn update_filter_frame(
+        &mut self,
+        time: f64,
+        inframe: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    ) {
+        self.update_filter(time, inframe.as_u32_slice(), outframe.into_u32_slice());
+    }
+}
+
+/// A mixer2 plugin working on frames of its [Plugin::Pixel] type, the alternative to
+/// [Mixer2Plugin]. Implemented for every [Mixer2Plugin].
+pub trait Mixer2FramePlugin: Plugin<Kind = KindMixer2> {
+    fn update_mixer2_frame(
+        &mut self,
+        time: f64,
+        inframe1: Frame<'_, Self::Pixel>,
+        inframe2: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    );
+}
+
+impl<T: Mixer2Plugin> Mixer2FramePlugin for T {
+    fn update_mixer2_frame(
+        &mut self,
+        time: f64,
+        inframe1: Frame<'_, Self::Pixel>,
+        inframe2: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    ) {
+        self.update_mixer2(
+            time,
+            inframe1.as_u32_slice(),
+            inframe2.as_u32_slice(),
+            outframe.into_u32_slice(),
+        );
+    }
+}
+
+/// A mixer3 plugin working on frames of its [Plugin::Pixel] type, the alternative to
+/// [Mixer3Plugin]. Implemented for every [Mixer3Plugin].
+pub trait Mixer3FramePlugin: Plugin<Kind = KindMixer3> {
+    fn update_mixer3_frame(
+        &mut self,
+        time: f64,
+        inframe1: Frame<'_, Self::Pixel>,
+        inframe2: Frame<'_, Self::Pixel>,
+        inframe3: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    );
+}
+
+impl<T: Mixer3Plugin> Mixer3FramePlugin for T {
+    fn update_mixer3_frame(
+        &mut self,
+        time: f64,
+        inframe1: Frame<'_, Self::Pixel>,
+        inframe2: Frame<'_, Self::Pixel>,
+        inframe3: Frame<'_, Self::Pixel>,
+        outframe: FrameMut<'_, Self::Pixel>,
+    ) {
+        self.update_mixer3(
+            time,
+            inframe1.as_u32_slice(),
+            inframe2.as_u32_slice(),
+            inframe3.as_u32_slice(),
+            outframe.into_u32_slice(),
+        );
+    }
+}
+
 /// The type returned by the plugin to tell the application about its name, type, number of
 /// parameters, and version.
+///
+/// The color model is determined by [Plugin::Pixel].
 #[derive(Debug, Clone, Copy)]
 pub struct PluginInfo {
     /// The (short) name of the plugin
     pub name: &'static CStr,
     /// The plugin author
     pub author: &'static CStr,
-    /// The color model used
-    pub color_model: ColorModel,
     /// The major version of the plugin
     pub major_version: i32,
     /// The minor version of the plugin
@@ -104,6 +405,8 @@ pub struct PluginInfo {
 
 /// List of supported color models.
 ///
+/// Plugins select their color model with the corresponding [Pixel] type.
+///
 /// Note: the color models are endian independent, because the color components are defined by
 /// their positon in memory, not by their significance in an uint32_t value.
 ///
@@ -149,13 +452,17 @@ pub enum ColorModel {
 }
 
 #[doc(hidden)]
-pub struct InstanceHolder<P: Plugin + PluginKindUpdate<P::Kind>> {
+pub struct InstanceHolder<P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>> {
+    frame_length: usize,
+    poisoned: AtomicBool,
     instance: Mutex<Instance<P>>,
 }
 
-impl<P: Plugin + PluginKindUpdate<P::Kind>> InstanceHolder<P> {
+impl<P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>> InstanceHolder<P> {
     pub fn new(instance: Instance<P>) -> Self {
         InstanceHolder {
+            frame_length: instance.frame_length(),
+            poisoned: AtomicBool::new(false),
             instance: Mutex::new(instance),
         }
     }
@@ -163,25 +470,352 @@ impl<P: Plugin + PluginKindUpdate<P::Kind>> InstanceHolder<P> {
     pub fn lock(&self) -> LockResult<MutexGuard<'_, Instance<P>>> {
         self.instance.lock()
     }
+
+    /// Whether the instance panicked and is no longer called into.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Acquire)
+    }
+
+    // Convert an instance handle received from the application, reporting null handles.
+    unsafe fn from_handle<'a>(
+        entry_point: &'static str,
+        instance: ffi::f0r_instance_t,
+    ) -> Option<&'a Self> {
+        let holder = unsafe { (instance as *const InstanceHolder<P>).as_ref() };
+        if holder.is_none() {
+            diagnostic::report(&Diagnostic::NullPointer {
+                entry_point,
+                argument: "instance",
+            });
+        }
+        holder
+    }
+
+    // Run `f` on the instance unless it is poisoned, poisoning it if `f` panics.
+    fn with_instance<R>(
+        &self,
+        entry_point: &'static str,
+        f: impl FnOnce(&mut Instance<P>) -> R,
+    ) -> Option<R> {
+        if self.is_poisoned() {
+            return None;
+        }
+        let Ok(mut instance) = self.lock() else {
+            self.poisoned.store(true, Ordering::Release);
+            return None;
+        };
+        let result = diagnostic::catch(entry_point, || f(&mut instance));
+        if result.is_none() {
+            self.poisoned.store(true, Ordering::Release);
+        }
+        result
+    }
+}
+
+// Implementations of the frei0r C API for plugin `P`. The functions exported by [plugin!] forward
+// to these, which also lets the in-process test host drive a plugin without loading it.
+//
+// Panics are caught here, they must never unwind into the host.
+#[doc(hidden)]
+impl<P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>> InstanceHolder<P> {
+    pub extern "C" fn f0r_init<G: PluginGlobal>() -> c_int {
+        diagnostic::catch("f0r_init", || {
+            // Build a runtime parameter list here rather than in the first parameter query.
+            if global::params::<P>().failed {
+                return 0;
+            }
+            match global::init::<G>() {
+                Ok(()) => 1,
+                Err(error) => {
+                    diagnostic::report(&Diagnostic::InitFailed {
+                        error: error.as_ref(),
+                    });
+                    0
+                }
+            }
+        })
+        .unwrap_or(0)
+    }
+
+    pub extern "C" fn f0r_deinit<G: PluginGlobal>() {
+        diagnostic::catch("f0r_deinit", global::deinit::<G>);
+    }
+
+    pub unsafe extern "C" fn f0r_get_plugin_info(info: *mut ffi::f0r_plugin_info_t) {
+        diagnostic::catch("f0r_get_plugin_info", || unsafe {
+            Instance::<P>::f0r_get_plugin_info(info)
+        });
+    }
+
+    pub unsafe extern "C" fn f0r_get_param_info(
+        info: *mut ffi::f0r_param_info_t,
+        param_index: c_int,
+    ) {
+        diagnostic::catch("f0r_get_param_info", || unsafe {
+            Instance::<P>::f0r_get_param_info(info, param_index)
+        });
+    }
+
+    pub extern "C" fn f0r_construct(width: c_uint, height: c_uint) -> ffi::f0r_instance_t {
+        diagnostic::catch("f0r_construct", || {
+            match Instance::<P>::try_new(width, height) {
+                Ok(instance) => {
+                    Box::into_raw(Box::new(InstanceHolder::new(instance))) as ffi::f0r_instance_t
+                }
+                Err(error) => {
+                    diagnostic::report(&Diagnostic::ConstructFailed {
+                        width,
+                        height,
+                        error: error.as_ref(),
+                    });
+                    std::ptr::null_mut()
+                }
+            }
+        })
+        .unwrap_or(std::ptr::null_mut())
+    }
+
+    pub unsafe extern "C" fn f0r_destruct(instance: ffi::f0r_instance_t) {
+        if unsafe { Self::from_handle("f0r_destruct", instance) }.is_none() {
+            return;
+        }
+        let holder = unsafe { Box::from_raw(instance as *mut InstanceHolder<P>) };
+        diagnostic::catch("f0r_destruct", || drop(holder));
+    }
+
+    pub unsafe extern "C" fn f0r_set_param_value(
+        instance: ffi::f0r_instance_t,
+        param: ffi::f0r_param_t,
+        param_index: c_int,
+    ) {
+        let Some(holder) = (unsafe { Self::from_handle("f0r_set_param_value", instance) }) else {
+            return;
+        };
+        holder.with_instance("f0r_set_param_value", |instance| unsafe {
+            instance.f0r_set_param_value(param, param_index)
+        });
+    }
+
+    pub unsafe extern "C" fn f0r_get_param_value(
+        instance: ffi::f0r_instance_t,
+        param: ffi::f0r_param_t,
+        param_index: c_int,
+    ) {
+        let Some(holder) = (unsafe { Self::from_handle("f0r_get_param_value", instance) }) else {
+            return;
+        };
+        holder.with_instance("f0r_get_param_value", |instance| unsafe {
+            instance.f0r_get_param_value(param, param_index)
+        });
+    }
+
+    pub unsafe extern "C" fn f0r_update(
+        instance: ffi::f0r_instance_t,
+        time: f64,
+        inframe: *const u32,
+        outframe: *mut u32,
+    ) {
+        unsafe {
+            Self::f0r_update2(
+                instance,
+                time,
+                inframe,
+                std::ptr::null(),
+                std::ptr::null(),
+                outframe,
+            )
+        };
+    }
+
+    pub unsafe extern "C" fn f0r_update2(
+        instance: ffi::f0r_instance_t,
+        time: f64,
+        inframe1: *const u32,
+        inframe2: *const u32,
+        inframe3: *const u32,
+        outframe: *mut u32,
+    ) {
+        let Some(holder) = (unsafe { Self::from_handle("f0r_update2", instance) }) else {
+            return;
+        };
+        let updated = holder.with_instance("f0r_update2", |instance| unsafe {
+            instance.f0r_update2(time, inframe1, inframe2, inframe3, outframe)
+        });
+        if updated != Some(true) && !outframe.is_null() {
+            // Do not hand a half written frame back to the host.
+            unsafe { std::ptr::write_bytes(outframe, 0, holder.frame_length) };
+        }
+    }
+}
+
+/// Build a [PluginInfo] from the Cargo package metadata of the crate invoking it.
+///
+/// | Field           | Source                    |
+/// |-----------------|---------------------------|
+/// | `name`          | `CARGO_PKG_NAME`          |
+/// | `author`        | `CARGO_PKG_AUTHORS`       |
+/// | `major_version` | `CARGO_PKG_VERSION_MAJOR` |
+/// | `minor_version` | `CARGO_PKG_VERSION_MINOR` |
+/// | `explanation`   | `CARGO_PKG_DESCRIPTION`, `None` if empty |
+///
+/// Cargo separates multiple authors with `:`. Any field can be overridden with `field: value`
+/// pairs. The defaults are evaluated at compile time.
+///
+/// ```
+/// # use frei0r_rs2::*;
+/// let info = plugin_info!(name: c"invert0r");
+/// assert_eq!(info.name, c"invert0r");
+/// assert_eq!(info.major_version, env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap());
+/// ```
+#[macro_export]
+macro_rules! plugin_info {
+    ($($field:ident : $value:expr),* $(,)?) => {
+        $crate::PluginInfo {
+            $($field: $value,)*
+            ..const {
+                $crate::PluginInfo {
+                    name: $crate::ffi::c_str(concat!(env!("CARGO_PKG_NAME"), "\0")),
+                    author: $crate::ffi::c_str(concat!(env!("CARGO_PKG_AUTHORS"), "\0")),
+                    major_version: $crate::ffi::version(env!("CARGO_PKG_VERSION_MAJOR")),
+                    minor_version: $crate::ffi::version(env!("CARGO_PKG_VERSION_MINOR")),
+                    explanation: $crate::ffi::c_str_non_empty(concat!(
+                        env!("CARGO_PKG_DESCRIPTION"),
+                        "\0"
+                    )),
+                }
+            }
+        }
+    };
 }
 
 /// Export necessary C bindings for frei0r plugin.
+///
+/// `plugin!(MyPlugin, global = MyState)` also creates the [PluginGlobal] state `MyState` in
+/// `f0r_init` and releases it in `f0r_deinit`.
+///
+/// The plugin declaration is checked against the frei0r specification at compile time. The
+/// build fails if
+/// - a [KindSource] plugin uses the [ColorModel::PACKED32] color model,
+/// - a name in [Plugin::PARAMS] is empty or used more than once,
+/// - a name in [Plugin::PARAMS] starts with the name of a composite parameter and a dot, it
+///   would clash with the `<parameter>.<field>` names of its fields,
+/// - the number of parameters does not fit in a `c_int`,
+/// - a parameter [Range] is empty or not finite, a default lies outside its range or a step is
+///   not positive,
+/// - the explanation of a parameter that is not an output starts with `(read-only)`, the marker
+///   of [output parameters](ParamInfo::as_output).
+///
+/// Parameter names must be unique:
+///
+/// ```compile_fail,E0080
+/// # use frei0r_rs2::*;
+/// struct Duplicate(f64);
+///
+/// impl Plugin for Duplicate {
+///     type Kind = KindSource;
+///     type Pixel = Rgba8;
+///     const PARAMS: &'static [ParamInfo<Self>] = &[
+///         ParamInfo::new_double(c"amount", c"", |p| p.0, |p, v| p.0 = v),
+///         ParamInfo::new_double(c"amount", c"", |p| p.0, |p, v| p.0 = v),
+///     ];
+/// #   fn info() -> PluginInfo {
+/// #       plugin_info!(name: c"duplicate")
+/// #   }
+/// #   fn new(_width: usize, _height: usize) -> Self {
+/// #       Duplicate(0.0)
+/// #   }
+/// }
+/// # impl SourcePlugin for Duplicate {
+/// #     fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+/// #         outframe.fill(0);
+/// #     }
+/// # }
+///
+/// plugin!(Duplicate);
+/// # fn main() {}
+/// ```
+///
+/// Parameter names must not be empty:
+///
+/// ```compile_fail,E0080
+/// # use frei0r_rs2::*;
+/// struct Unnamed(f64);
+///
+/// impl Plugin for Unnamed {
+///     type Kind = KindSource;
+///     type Pixel = Rgba8;
+///     const PARAMS: &'static [ParamInfo<Self>] =
+///         &[ParamInfo::new_double(c"", c"", |p| p.0, |p, v| p.0 = v)];
+/// #   fn info() -> PluginInfo {
+/// #       plugin_info!(name: c"unnamed")
+/// #   }
+/// #   fn new(_width: usize, _height: usize) -> Self {
+/// #       Unnamed(0.0)
+/// #   }
+/// }
+/// # impl SourcePlugin for Unnamed {
+/// #     fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+/// #         outframe.fill(0);
+/// #     }
+/// # }
+///
+/// plugin!(Unnamed);
+/// # fn main() {}
+/// ```
+///
+/// Sources must not use the packed color model, the application could not tell the layout of
+/// the frames they produce:
+///
+/// ```compile_fail,E0080
+/// # use frei0r_rs2::*;
+/// struct Noise;
+///
+/// impl Plugin for Noise {
+///     type Kind = KindSource;
+///     type Pixel = Packed32;
+/// #   fn info() -> PluginInfo {
+/// #       plugin_info!(name: c"noise")
+/// #   }
+/// #   fn new(_width: usize, _height: usize) -> Self {
+/// #       Noise
+/// #   }
+/// }
+/// # impl SourcePlugin for Noise {
+/// #     fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+/// #         outframe.fill(0);
+/// #     }
+/// # }
+///
+/// plugin!(Noise);
+/// # fn main() {}
+/// ```
 #[macro_export]
 macro_rules! plugin {
     ($type:ty) => {
+        $crate::plugin!($type, global = ());
+    };
+    ($type:ty, global = $global:ty) => {
         use $crate::{InstanceHolder, ffi};
 
+        const _: () = ffi::assert_valid::<$type>();
+
+        // Marks the library as built with this crate, see LoadedPlugin::built_with_crate.
+        #[unsafe(no_mangle)]
+        pub extern "C" fn frei0r_rs2_plugin() {}
+
         #[unsafe(no_mangle)]
         pub extern "C" fn f0r_init() -> std::ffi::c_int {
-            1
+            InstanceHolder::<$type>::f0r_init::<$global>()
         }
 
         #[unsafe(no_mangle)]
-        pub extern "C" fn f0r_deinit() {}
+        pub extern "C" fn f0r_deinit() {
+            InstanceHolder::<$type>::f0r_deinit::<$global>()
+        }
 
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn f0r_get_plugin_info(info: *mut ffi::f0r_plugin_info_t) {
-            unsafe { ffi::Instance::<$type>::f0r_get_plugin_info(info) };
+            unsafe { InstanceHolder::<$type>::f0r_get_plugin_info(info) };
         }
 
         #[unsafe(no_mangle)]
@@ -189,7 +823,7 @@ macro_rules! plugin {
             info: *mut ffi::f0r_param_info_t,
             param_index: std::ffi::c_int,
         ) {
-            unsafe { ffi::Instance::<$type>::f0r_get_param_info(info, param_index) };
+            unsafe { InstanceHolder::<$type>::f0r_get_param_info(info, param_index) };
         }
 
         #[unsafe(no_mangle)]
@@ -197,38 +831,30 @@ macro_rules! plugin {
             width: std::ffi::c_uint,
             height: std::ffi::c_uint,
         ) -> ffi::f0r_instance_t {
-            let holder = InstanceHolder::new(ffi::Instance::<$type>::new(width, height));
-            Box::into_raw(Box::new(holder)) as ffi::f0r_instance_t
+            InstanceHolder::<$type>::f0r_construct(width, height)
         }
 
         #[unsafe(no_mangle)]
-        pub extern "C" fn f0r_destruct(instance: ffi::f0r_instance_t) {
-            let holder = unsafe { Box::from_raw(instance as *mut InstanceHolder<$type>) };
-            drop(holder)
+        pub unsafe extern "C" fn f0r_destruct(instance: ffi::f0r_instance_t) {
+            unsafe { InstanceHolder::<$type>::f0r_destruct(instance) };
         }
 
         #[unsafe(no_mangle)]
-        pub extern "C" fn f0r_set_param_value(
+        pub unsafe extern "C" fn f0r_set_param_value(
             instance: ffi::f0r_instance_t,
             param: ffi::f0r_param_t,
             param_index: std::ffi::c_int,
         ) {
-            let holder = unsafe { &*(instance as *const InstanceHolder<$type>) };
-            if let Ok(mut instance) = holder.lock() {
-                instance.f0r_set_param_value(param, param_index);
-            }
+            unsafe { InstanceHolder::<$type>::f0r_set_param_value(instance, param, param_index) };
         }
 
         #[unsafe(no_mangle)]
-        pub extern "C" fn f0r_get_param_value(
+        pub unsafe extern "C" fn f0r_get_param_value(
             instance: ffi::f0r_instance_t,
             param: ffi::f0r_param_t,
             param_index: std::ffi::c_int,
         ) {
-            let holder = unsafe { &*(instance as *const InstanceHolder<$type>) };
-            if let Ok(mut instance) = holder.lock() {
-                instance.f0r_get_param_value(param, param_index);
-            }
+            unsafe { InstanceHolder::<$type>::f0r_get_param_value(instance, param, param_index) };
         }
 
         #[unsafe(no_mangle)]
@@ -238,18 +864,7 @@ macro_rules! plugin {
             inframe: *const u32,
             outframe: *mut u32,
         ) {
-            let holder = unsafe { &*(instance as *const InstanceHolder<$type>) };
-            if let Ok(mut instance) = holder.lock() {
-                unsafe {
-                    instance.f0r_update2(
-                        time,
-                        inframe,
-                        std::ptr::null(),
-                        std::ptr::null(),
-                        outframe,
-                    )
-                };
-            }
+            unsafe { InstanceHolder::<$type>::f0r_update(instance, time, inframe, outframe) };
         }
 
         #[unsafe(no_mangle)]
@@ -261,10 +876,11 @@ macro_rules! plugin {
             inframe3: *const u32,
             outframe: *mut u32,
         ) {
-            let holder = unsafe { &*(instance as *const InstanceHolder<$type>) };
-            if let Ok(mut instance) = holder.lock() {
-                unsafe { instance.f0r_update2(time, inframe1, inframe2, inframe3, outframe) };
-            }
+            unsafe {
+                InstanceHolder::<$type>::f0r_update2(
+                    instance, time, inframe1, inframe2, inframe3, outframe,
+                )
+            };
         }
     };
 }
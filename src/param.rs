@@ -1,9 +1,13 @@
-use std::ffi::CStr;
+use crate::ffi::f0r_param_t;
+use crate::resource::PathValue;
+use crate::smoothing::Smoothing;
+use std::ffi::{CStr, c_int};
 
 /// Color parameter.
 ///
 /// All components are in the range [0, 1].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     /// Red component.
     pub r: f32,
@@ -16,12 +20,40 @@ pub struct Color {
 /// Position parameter.
 ///
 /// All coordinates are in the range [0, 1].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub x: f64,
     pub y: f64,
 }
 
+/// Physical range of a double or position parameter, see [ParamInfo::with_range].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Range {
+    /// Map a normalized value from the application into the range, clamping it to [0, 1] first.
+    pub fn denormalize(&self, value: f64) -> f64 {
+        self.min + value.clamp(0.0, 1.0) * (self.max - self.min)
+    }
+
+    /// Map a value in the range to the normalized value reported to the application.
+    pub fn normalize(&self, value: f64) -> f64 {
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+}
+
+/// Default value of a parameter, in the units of its getter and setter.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParamDefault {
+    Double(f64),
+    Position(Position),
+}
+
 /// Type of a parameter.
 #[derive(Debug)]
 pub enum ParamKind<T> {
@@ -33,6 +65,11 @@ pub enum ParamKind<T> {
         get: fn(&T) -> f64,
         set: fn(&mut T, f64),
     },
+    /// See [ParamInfo::new_int].
+    Int {
+        get: fn(&T) -> i32,
+        set: fn(&mut T, i32),
+    },
     Color {
         get: fn(&T) -> Color,
         set: fn(&mut T, &Color),
@@ -45,6 +82,319 @@ pub enum ParamKind<T> {
         get: fn(&T) -> &CStr,
         set: fn(&mut T, &CStr),
     },
+    /// See [ParamInfo::new_string_owned].
+    OwnedString {
+        get: fn(&T) -> &str,
+        set: fn(&mut T, String),
+    },
+    /// See [ParamInfo::new_path].
+    Path {
+        get: fn(&T) -> &dyn PathValue,
+        get_mut: fn(&mut T) -> &mut dyn PathValue,
+    },
+    /// See [ParamInfo::new_choice].
+    Choice(ChoiceParam<T>),
+    /// See [ParamInfo::new_composite].
+    Composite(CompositeParam<T>),
+}
+
+/// A set of parameters, by index in the parameter list of a plugin.
+///
+/// See [Plugin::params_changed](crate::Plugin::params_changed).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamSet {
+    bits: Vec<u64>,
+}
+
+impl ParamSet {
+    /// The set of parameters `0..len`.
+    pub(crate) fn all(len: usize) -> Self {
+        let mut set = ParamSet::default();
+        (0..len).for_each(|index| set.insert(index));
+        set
+    }
+
+    pub(crate) fn insert(&mut self, index: usize) {
+        let word = index / 64;
+        if self.bits.len() <= word {
+            self.bits.resize(word + 1, 0);
+        }
+        self.bits[word] |= 1 << (index % 64);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// Whether the parameter at `index` is in the set.
+    pub fn contains(&self, index: usize) -> bool {
+        self.bits
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The indices in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.bits.len() * 64).filter(|&index| self.contains(index))
+    }
+}
+
+/// A Rust enum usable as a choice parameter, see [ParamInfo::new_choice].
+///
+/// ```
+/// # use frei0r_rs2::Choice;
+/// # use std::ffi::CStr;
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum BlendMode {
+///     Normal,
+///     Multiply,
+///     Screen,
+/// }
+///
+/// impl Choice for BlendMode {
+///     const CHOICES: &'static [(&'static CStr, Self)] = &[
+///         (c"normal", BlendMode::Normal),
+///         (c"multiply", BlendMode::Multiply),
+///         (c"screen", BlendMode::Screen),
+///     ];
+/// }
+/// ```
+pub trait Choice: 'static + Copy + PartialEq {
+    /// Every value with the name used by [ChoiceEncoding::String], in the order used by
+    /// [ChoiceEncoding::Double].
+    const CHOICES: &'static [(&'static CStr, Self)];
+}
+
+/// How a choice parameter is presented to the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceEncoding {
+    /// A string parameter holding the name of the choice.
+    String,
+    /// A double parameter, [0, 1] is divided into one bucket per choice.
+    ///
+    /// Choice `i` of `n` is reported as `i / (n - 1)`, a value `v` selects choice
+    /// `floor(v * n)` clamped to the valid choices.
+    Double,
+}
+
+/// Accessors of a choice parameter, with the [Choice] type erased.
+pub struct ChoiceParam<T> {
+    encoding: ChoiceEncoding,
+    len: usize,
+    name: fn(usize) -> &'static CStr,
+    // The getter and setter passed to new_choice, only ever called through `get_index` and
+    // `set_index` which were instantiated for the same choice type.
+    get: fn(),
+    set: fn(),
+    get_index: unsafe fn(fn(), &T) -> usize,
+    set_index: unsafe fn(fn(), &mut T, usize),
+}
+
+impl<T> ChoiceParam<T> {
+    /// How the choice is presented to the application.
+    pub fn encoding(&self) -> ChoiceEncoding {
+        self.encoding
+    }
+
+    /// The names of the valid choices, in order.
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &'static CStr> + '_ {
+        (0..self.len).map(self.name)
+    }
+
+    /// Index of the current choice.
+    pub(crate) fn get(&self, plugin: &T) -> usize {
+        unsafe { (self.get_index)(self.get, plugin) }
+    }
+
+    /// Select the choice at `index`, which must be less than the number of choices.
+    pub(crate) fn set(&self, plugin: &mut T, index: usize) {
+        assert!(index < self.len, "choice index out of range");
+        unsafe { (self.set_index)(self.set, plugin, index) }
+    }
+
+    /// Decode a value received from the application, `None` if it names no choice.
+    pub(crate) fn decode_name(&self, name: &CStr) -> Option<usize> {
+        self.names().position(|choice| choice == name)
+    }
+
+    pub(crate) fn decode_double(&self, value: f64) -> Option<usize> {
+        if value.is_nan() {
+            return None;
+        }
+        let index = (value.clamp(0.0, 1.0) * self.len as f64).floor() as usize;
+        Some(index.min(self.len - 1))
+    }
+
+    pub(crate) fn encode_double(&self, index: usize) -> f64 {
+        if self.len > 1 {
+            index as f64 / (self.len - 1) as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ChoiceParam<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChoiceParam")
+            .field("encoding", &self.encoding)
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A structured value exposed to the application as several frei0r parameters, see
+/// [ParamInfo::new_composite].
+///
+/// ```
+/// # use frei0r_rs2::{Composite, ParamInfo};
+/// #[derive(Clone, Copy)]
+/// struct Rect {
+///     x: f64,
+///     y: f64,
+///     width: f64,
+///     height: f64,
+/// }
+///
+/// impl Composite for Rect {
+///     const FIELDS: &'static [ParamInfo<Self>] = &[
+///         ParamInfo::new_double(c"x", c"Left edge", |r| r.x, |r, v| r.x = v),
+///         ParamInfo::new_double(c"y", c"Top edge", |r| r.y, |r, v| r.y = v),
+///         ParamInfo::new_double(c"w", c"Width", |r| r.width, |r, v| r.width = v),
+///         ParamInfo::new_double(c"h", c"Height", |r| r.height, |r, v| r.height = v),
+///     ];
+/// }
+/// ```
+pub trait Composite: 'static + Copy {
+    /// The fields, each exposed as one frei0r parameter named `<parameter>.<field>`.
+    ///
+    /// String parameters and nested composites are not supported. A field with an empty
+    /// explanation uses the explanation of the composite parameter.
+    const FIELDS: &'static [ParamInfo<Self>];
+}
+
+type SetField<T> = unsafe fn(fn(), fn(), &mut T, usize, f0r_param_t, c_int) -> bool;
+
+/// Accessors of a composite parameter, with the [Composite] type erased.
+pub struct CompositeParam<T> {
+    len: usize,
+    // The getter and setter passed to new_composite, see ChoiceParam.
+    get: fn(),
+    set: fn(),
+    field_name: fn(usize) -> &'static CStr,
+    field_explanation: fn(usize) -> &'static CStr,
+    set_field: SetField<T>,
+    get_field: unsafe fn(fn(), &T, usize, f0r_param_t),
+    field_type: fn(usize) -> c_int,
+}
+
+impl<T> CompositeParam<T> {
+    /// The names of the fields, relative to the name of the composite parameter.
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &'static CStr> + '_ {
+        (0..self.len).map(self.field_name)
+    }
+
+    pub(crate) fn field_explanation(&self, field: usize) -> &'static CStr {
+        (self.field_explanation)(field)
+    }
+
+    pub(crate) fn field_type(&self, field: usize) -> c_int {
+        (self.field_type)(field)
+    }
+
+    pub(crate) unsafe fn set_field(
+        &self,
+        plugin: &mut T,
+        field: usize,
+        param: f0r_param_t,
+        param_index: c_int,
+    ) -> bool {
+        unsafe { (self.set_field)(self.get, self.set, plugin, field, param, param_index) }
+    }
+
+    pub(crate) unsafe fn get_field(&self, plugin: &T, field: usize, param: f0r_param_t) {
+        unsafe { (self.get_field)(self.get, plugin, field, param) }
+    }
+}
+
+impl<T> std::fmt::Debug for CompositeParam<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeParam")
+            .field("names", &self.names().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+fn composite_field_name<C: Composite>(field: usize) -> &'static CStr {
+    C::FIELDS[field].name
+}
+
+fn composite_field_explanation<C: Composite>(field: usize) -> &'static CStr {
+    C::FIELDS[field].explanation
+}
+
+fn composite_field_type<C: Composite>(field: usize) -> c_int {
+    crate::ffi::param_type(&C::FIELDS[field])
+}
+
+unsafe fn composite_set_field<T, C: Composite>(
+    get: fn(),
+    set: fn(),
+    plugin: &mut T,
+    field: usize,
+    param: f0r_param_t,
+    param_index: c_int,
+) -> bool {
+    let get = unsafe { std::mem::transmute::<fn(), fn(&T) -> C>(get) };
+    let set = unsafe { std::mem::transmute::<fn(), fn(&mut T, C)>(set) };
+    let mut value = get(plugin);
+    let applied =
+        unsafe { crate::ffi::set_param(&C::FIELDS[field], &mut value, param, param_index) };
+    if applied {
+        set(plugin, value);
+    }
+    applied
+}
+
+unsafe fn composite_get_field<T, C: Composite>(
+    get: fn(),
+    plugin: &T,
+    field: usize,
+    param: f0r_param_t,
+) {
+    let get = unsafe { std::mem::transmute::<fn(), fn(&T) -> C>(get) };
+    let value = get(plugin);
+    // Composite fields are never strings, the buffer is not used.
+    unsafe { crate::ffi::get_param(&C::FIELDS[field], &value, param, &mut Vec::new()) }
+}
+
+fn choice_name<C: Choice>(index: usize) -> &'static CStr {
+    C::CHOICES[index].0
+}
+
+unsafe fn choice_get_index<T, C: Choice>(get: fn(), plugin: &T) -> usize {
+    let get = unsafe { std::mem::transmute::<fn(), fn(&T) -> C>(get) };
+    let value = get(plugin);
+    C::CHOICES
+        .iter()
+        .position(|(_, choice)| *choice == value)
+        .unwrap_or(0)
+}
+
+unsafe fn choice_set_index<T, C: Choice>(set: fn(), plugin: &mut T, index: usize) {
+    let set = unsafe { std::mem::transmute::<fn(), fn(&mut T, C)>(set) };
+    set(plugin, C::CHOICES[index].1);
 }
 
 macro_rules! param_info_new {
@@ -61,6 +411,12 @@ macro_rules! param_info_new {
                     name,
                     explanation,
                     kind: ParamKind::[<$type:camel>] { get, set },
+                    range: None,
+                    step: None,
+                    unit: None,
+                    default: None,
+                    smoothing: Smoothing::None,
+                    output: false,
                 }
             }
         }
@@ -73,6 +429,12 @@ pub struct ParamInfo<T> {
     name: &'static CStr,
     explanation: &'static CStr,
     kind: ParamKind<T>,
+    range: Option<Range>,
+    step: Option<f64>,
+    unit: Option<&'static CStr>,
+    default: Option<ParamDefault>,
+    smoothing: Smoothing,
+    output: bool,
 }
 
 impl<T> ParamInfo<T> {
@@ -82,15 +444,539 @@ impl<T> ParamInfo<T> {
     param_info_new!(position, Position, &Position);
     param_info_new!(string, &CStr, &CStr);
 
-    pub(crate) fn name(&self) -> &'static CStr {
+    /// Create a string parameter backed by a Rust [String].
+    ///
+    /// Values from the application are converted lossily from their C encoding, values
+    /// returned to it end at the first NUL. The value handed to the application is a copy owned
+    /// by the instance, so the plugin is free to change or drop its string.
+    pub const fn new_string_owned(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        get: fn(&T) -> &str,
+        set: fn(&mut T, String),
+    ) -> Self {
+        ParamInfo {
+            name,
+            explanation,
+            kind: ParamKind::OwnedString { get, set },
+            range: None,
+            step: None,
+            unit: None,
+            default: None,
+            smoothing: Smoothing::None,
+            output: false,
+        }
+    }
+
+    /// Create a path parameter loading a [Resource](crate::Resource) from the file it names.
+    ///
+    /// The application sees a string parameter. A new path is only recorded by the parameter
+    /// call, the file is loaded at the start of the next update so the application's thread is
+    /// not blocked while setting parameters. Loaded resources are cached by path and
+    /// modification time and shared between instances, failures are reported as a
+    /// [Diagnostic::LoadFailed](crate::Diagnostic::LoadFailed) and kept in the
+    /// [ResourcePath]. Setting the same path again loads the file again if the last load failed
+    /// or the file was modified since.
+    ///
+    /// ```
+    /// # use frei0r_rs2::{BoxError, ParamInfo, Resource, ResourcePath};
+    /// # use std::path::Path;
+    /// struct Lut(Vec<u8>);
+    ///
+    /// impl Resource for Lut {
+    ///     fn load(path: &Path) -> Result<Self, BoxError> {
+    ///         Ok(Lut(std::fs::read(path)?))
+    ///     }
+    /// }
+    ///
+    /// struct LutPlugin {
+    ///     lut: ResourcePath<Lut>,
+    /// }
+    ///
+    /// const LUT: ParamInfo<LutPlugin> = ParamInfo::new_path(
+    ///     c"lut",
+    ///     c"Lookup table file",
+    ///     |plugin| &plugin.lut,
+    ///     |plugin| &mut plugin.lut,
+    /// );
+    /// ```
+    ///
+    /// [ResourcePath]: crate::ResourcePath
+    pub const fn new_path(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        get: fn(&T) -> &dyn PathValue,
+        get_mut: fn(&mut T) -> &mut dyn PathValue,
+    ) -> Self {
+        ParamInfo {
+            name,
+            explanation,
+            kind: ParamKind::Path { get, get_mut },
+            range: None,
+            step: None,
+            unit: None,
+            default: None,
+            smoothing: Smoothing::None,
+            output: false,
+        }
+    }
+
+    /// Create an integer parameter in `min..=max`.
+    ///
+    /// The application sees a double normalized to [0, 1] which is mapped into the range and
+    /// rounded to the nearest integer.
+    pub const fn new_int(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        min: i32,
+        max: i32,
+        get: fn(&T) -> i32,
+        set: fn(&mut T, i32),
+    ) -> Self {
+        ParamInfo {
+            name,
+            explanation,
+            kind: ParamKind::Int { get, set },
+            range: Some(Range {
+                min: min as f64,
+                max: max as f64,
+            }),
+            step: Some(1.0),
+            unit: None,
+            default: None,
+            smoothing: Smoothing::None,
+            output: false,
+        }
+    }
+
+    /// Create an angle parameter in radians, in `0..=2π` unless changed with
+    /// [Self::with_range].
+    pub const fn new_angle(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        get: fn(&T) -> f64,
+        set: fn(&mut T, f64),
+    ) -> Self {
+        Self::new_double(name, explanation, get, set)
+            .with_range(0.0, std::f64::consts::TAU)
+            .with_unit(c"rad")
+    }
+
+    /// Create a percentage parameter in `0..=100`.
+    pub const fn new_percent(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        get: fn(&T) -> f64,
+        set: fn(&mut T, f64),
+    ) -> Self {
+        Self::new_double(name, explanation, get, set)
+            .with_range(0.0, 100.0)
+            .with_unit(c"%")
+    }
+
+    /// Create a choice parameter selecting one of the values of `C`.
+    ///
+    /// # Panics
+    ///
+    /// At compile time if `C` has no choices or a name is empty or used more than once.
+    pub const fn new_choice<C: Choice>(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        encoding: ChoiceEncoding,
+        get: fn(&T) -> C,
+        set: fn(&mut T, C),
+    ) -> Self {
+        assert!(!C::CHOICES.is_empty(), "choice parameters need a choice");
+        let mut i = 0;
+        while i < C::CHOICES.len() {
+            let choice = C::CHOICES[i].0.to_bytes();
+            assert!(!choice.is_empty(), "choice names must not be empty");
+            let mut j = 0;
+            while j < i {
+                assert!(
+                    !crate::ffi::bytes_eq(choice, C::CHOICES[j].0.to_bytes()),
+                    "choice names must be unique"
+                );
+                j += 1;
+            }
+            i += 1;
+        }
+        ParamInfo {
+            name,
+            explanation,
+            kind: ParamKind::Choice(ChoiceParam {
+                encoding,
+                len: C::CHOICES.len(),
+                name: choice_name::<C>,
+                // Function pointers all have the same size and are transmuted back to the same
+                // type by choice_get_index and choice_set_index.
+                get: unsafe { std::mem::transmute::<fn(&T) -> C, fn()>(get) },
+                set: unsafe { std::mem::transmute::<fn(&mut T, C), fn()>(set) },
+                get_index: choice_get_index::<T, C>,
+                set_index: choice_set_index::<T, C>,
+            }),
+            range: None,
+            step: None,
+            unit: None,
+            default: None,
+            smoothing: Smoothing::None,
+            output: false,
+        }
+    }
+
+    /// Create a composite parameter, exposed to the application as one frei0r parameter per
+    /// field of `C`.
+    ///
+    /// The fields follow each other in the frei0r parameter indices, so later parameters are
+    /// shifted accordingly. Setting a field reads the whole value with `get`, updates the field
+    /// and writes it back with `set`.
+    ///
+    /// # Panics
+    ///
+    /// At compile time if `C` has no fields, a field name is empty or used more than once, a
+    /// field is a string, path or composite parameter or is smoothed, a field explanation starts
+    /// with `(read-only)`, or the range, default or step of a field is invalid, as checked by
+    /// [plugin!](crate::plugin) for parameters:
+    ///
+    /// ```compile_fail,E0080
+    /// # use frei0r_rs2::*;
+    /// #[derive(Clone, Copy)]
+    /// struct Count(i32);
+    ///
+    /// impl Composite for Count {
+    ///     const FIELDS: &'static [ParamInfo<Self>] =
+    ///         &[ParamInfo::new_int(c"n", c"", 5, 5, |c| c.0, |c, v| c.0 = v)];
+    /// }
+    ///
+    /// const COUNT: ParamInfo<Count> = ParamInfo::new_composite(c"count", c"", |c| *c, |c, v| *c = v);
+    /// ```
+    pub const fn new_composite<C: Composite>(
+        name: &'static CStr,
+        explanation: &'static CStr,
+        get: fn(&T) -> C,
+        set: fn(&mut T, C),
+    ) -> Self {
+        assert!(!C::FIELDS.is_empty(), "composite parameters need a field");
+        let mut i = 0;
+        while i < C::FIELDS.len() {
+            let field = &C::FIELDS[i];
+            assert!(
+                !matches!(
+                    field.kind,
+                    ParamKind::String { .. }
+                        | ParamKind::OwnedString { .. }
+                        | ParamKind::Path { .. }
+                        | ParamKind::Composite(_)
+                ),
+                "composite fields must not be string, path or composite parameters"
+            );
+            assert!(
+                matches!(field.smoothing, Smoothing::None),
+                "composite fields can not be smoothed"
+            );
+            crate::ffi::validate_range(field);
+            let field_name = field.name.to_bytes();
+            assert!(!field_name.is_empty(), "field names must not be empty");
+            // Fields of output parameters are marked read-only when reported.
+            assert!(
+                !crate::ffi::is_read_only(field.explanation.to_bytes()),
+                "field explanations must not start with (read-only)"
+            );
+            let mut j = 0;
+            while j < i {
+                assert!(
+                    !crate::ffi::bytes_eq(field_name, C::FIELDS[j].name.to_bytes()),
+                    "field names must be unique"
+                );
+                j += 1;
+            }
+            i += 1;
+        }
+        ParamInfo {
+            name,
+            explanation,
+            kind: ParamKind::Composite(CompositeParam {
+                len: C::FIELDS.len(),
+                // See new_choice.
+                get: unsafe { std::mem::transmute::<fn(&T) -> C, fn()>(get) },
+                set: unsafe { std::mem::transmute::<fn(&mut T, C), fn()>(set) },
+                field_name: composite_field_name::<C>,
+                field_explanation: composite_field_explanation::<C>,
+                set_field: composite_set_field::<T, C>,
+                get_field: composite_get_field::<T, C>,
+                field_type: composite_field_type::<C>,
+            }),
+            range: None,
+            step: None,
+            unit: None,
+            default: None,
+            smoothing: Smoothing::None,
+            output: false,
+        }
+    }
+
+    /// Number of frei0r parameters this parameter is exposed as.
+    pub const fn frei0r_count(&self) -> usize {
+        match &self.kind {
+            ParamKind::Composite(composite) => composite.len,
+            _ => 1,
+        }
+    }
+
+    /// Set the physical range of a double or position parameter.
+    ///
+    /// The application keeps seeing values normalized to [0, 1], the getter and setter work with
+    /// values in `min..=max` instead. Values set by the application are clamped to [0, 1] and
+    /// NaN is ignored. The range applies to both coordinates of a position.
+    ///
+    /// The closures passed to the constructor can not be inferred through a method call, write
+    /// `ParamInfo::<Self>::new_double(...).with_range(...)`.
+    pub const fn with_range(self, min: f64, max: f64) -> Self {
+        assert!(
+            self.is_numeric(),
+            "with_range requires a double or position parameter"
+        );
+        ParamInfo {
+            range: Some(Range { min, max }),
+            ..self
+        }
+    }
+
+    /// Set the increment user interfaces should use, in the units of the range.
+    pub const fn with_step(self, step: f64) -> Self {
+        assert!(
+            self.is_numeric(),
+            "with_step requires a double or position parameter"
+        );
+        ParamInfo {
+            step: Some(step),
+            ..self
+        }
+    }
+
+    /// Set the display unit, e.g. `c"px"` or `c"°"`.
+    pub const fn with_unit(self, unit: &'static CStr) -> Self {
+        ParamInfo {
+            unit: Some(unit),
+            ..self
+        }
+    }
+
+    /// Set the default value of a double parameter.
+    ///
+    /// This is metadata for tools, [Plugin::new](crate::Plugin::new) must still set it.
+    pub const fn with_default(self, default: f64) -> Self {
+        assert!(
+            matches!(self.kind, ParamKind::Double { .. }),
+            "with_default requires a double parameter"
+        );
+        ParamInfo {
+            default: Some(ParamDefault::Double(default)),
+            ..self
+        }
+    }
+
+    /// Set the default value of a position parameter.
+    ///
+    /// This is metadata for tools, [Plugin::new](crate::Plugin::new) must still set it.
+    pub const fn with_default_position(self, default: Position) -> Self {
+        assert!(
+            matches!(self.kind, ParamKind::Position { .. }),
+            "with_default_position requires a position parameter"
+        );
+        ParamInfo {
+            default: Some(ParamDefault::Position(default)),
+            ..self
+        }
+    }
+
+    /// Smooth the values of a double or position parameter seen by the plugin.
+    ///
+    /// Values set by the application become the target the plugin's value moves towards during
+    /// the following updates, the setter is called with the intermediate values and
+    /// [Plugin::params_changed](crate::Plugin::params_changed) reports them. The application
+    /// reads back the target. Going back in time, e.g. when seeking, jumps to the target.
+    ///
+    /// # Panics
+    ///
+    /// At compile time if a duration is not positive, or the parameter is part of a
+    /// [Composite].
+    pub const fn with_smoothing(self, smoothing: Smoothing) -> Self {
+        assert!(
+            self.is_numeric(),
+            "with_smoothing requires a double or position parameter"
+        );
+        assert!(!self.output, "output parameters can not be smoothed");
+        match smoothing {
+            Smoothing::Exponential {
+                half_life: duration,
+            }
+            | Smoothing::CriticallyDamped {
+                smooth_time: duration,
+            } => assert!(duration > 0.0, "smoothing durations must be positive"),
+            Smoothing::None | Smoothing::Linear { .. } => {}
+        }
+        ParamInfo { smoothing, ..self }
+    }
+
+    /// Make this a read-only output parameter, which reports a value produced by the plugin,
+    /// e.g. a measurement made during the update.
+    ///
+    /// The application can only read it, values it sets are ignored and the explanation
+    /// reported to it starts with `(read-only)`. The setter is never called, it may do nothing.
+    ///
+    /// This deviates from frei0r.h, which requires `f0r_update` not to change the values
+    /// returned by `f0r_get_param_value`. Applications that cache parameter values instead of
+    /// reading them after each update show stale values.
+    ///
+    /// ```
+    /// # use frei0r_rs2::ParamInfo;
+    /// struct Meter {
+    ///     peak: f64,
+    /// }
+    ///
+    /// const PEAK: ParamInfo<Meter> = ParamInfo::<Meter>::new_double(
+    ///     c"peak",
+    ///     c"Brightest pixel",
+    ///     |meter| meter.peak,
+    ///     |_, _| {},
+    /// )
+    /// .as_output();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// At compile time for path and smoothed parameters.
+    pub const fn as_output(self) -> Self {
+        assert!(
+            !matches!(self.kind, ParamKind::Path { .. }),
+            "path parameters can not be outputs"
+        );
+        assert!(
+            matches!(self.smoothing, Smoothing::None),
+            "output parameters can not be smoothed"
+        );
+        ParamInfo {
+            output: true,
+            ..self
+        }
+    }
+
+    const fn is_numeric(&self) -> bool {
+        matches!(
+            self.kind,
+            ParamKind::Double { .. } | ParamKind::Position { .. }
+        )
+    }
+
+    /// The name reported to the application.
+    pub const fn name(&self) -> &'static CStr {
         self.name
     }
 
-    pub(crate) fn explanation(&self) -> &'static CStr {
+    /// The explanation reported to the application.
+    pub const fn explanation(&self) -> &'static CStr {
         self.explanation
     }
 
-    pub(crate) fn kind(&self) -> &ParamKind<T> {
+    /// The type of the parameter and its accessors.
+    pub const fn kind(&self) -> &ParamKind<T> {
         &self.kind
     }
+
+    /// The physical range, see [Self::with_range].
+    pub const fn range(&self) -> Option<Range> {
+        self.range
+    }
+
+    /// The increment user interfaces should use.
+    pub const fn step(&self) -> Option<f64> {
+        self.step
+    }
+
+    /// The display unit.
+    pub const fn unit(&self) -> Option<&'static CStr> {
+        self.unit
+    }
+
+    /// The default value.
+    pub const fn default(&self) -> Option<ParamDefault> {
+        self.default
+    }
+
+    /// The smoothing policy, see [Self::with_smoothing].
+    pub const fn smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// Whether this is a read-only output parameter, see [Self::as_output].
+    pub const fn is_output(&self) -> bool {
+        self.output
+    }
+}
+
+/// A parameter list generated from the fields of a struct.
+///
+/// With the `derive` feature enabled, `#[derive(Frei0rParams)]` implements this trait from fields
+/// annotated with `#[param(explanation = "...")]`, optionally with `name = "..."` (the field name
+/// is used otherwise). Supported field types are `bool`, `f64`, [Color], [Position], [String]
+/// and [CString](std::ffi::CString). Use the generated list as [Plugin::PARAMS](crate::Plugin::PARAMS).
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use frei0r_rs2::*;
+///
+/// #[derive(Frei0rParams)]
+/// struct Shift {
+///     #[param(explanation = "Shift in x direction")]
+///     xshift: f64,
+///     #[param(name = "y-shift", explanation = "Shift in y direction")]
+///     yshift: f64,
+/// }
+///
+/// impl Plugin for Shift {
+///     type Kind = KindFilter;
+///     type Pixel = Rgba8;
+///     const PARAMS: &'static [ParamInfo<Self>] = <Self as Frei0rParams>::PARAMS;
+/// #   fn info() -> PluginInfo {
+/// #       plugin_info!(name: c"shift")
+/// #   }
+/// #   fn new(_width: usize, _height: usize) -> Self {
+/// #       Shift { xshift: 0.0, yshift: 0.0 }
+/// #   }
+/// }
+/// # }
+/// ```
+///
+/// Fields of other types are rejected, at the field:
+///
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+```compile_fail
+use frei0r_rs2::*;
+
+#[derive(Frei0rParams)]
+struct Counter {
+    #[param(explanation = "Number of repetitions")]
+    count: u32,
+}
+```
+
+```compile_fail,E0308
+use frei0r_rs2::*;
+
+struct Color(u32);
+
+#[derive(Frei0rParams)]
+struct Tint {
+    #[param(explanation = "Not a frei0r_rs2::Color")]
+    color: Color,
+}
+```
+"#
+)]
+pub trait Frei0rParams: Sized + 'static {
+    /// The parameters, in field order.
+    const PARAMS: &'static [ParamInfo<Self>];
 }
@@ -0,0 +1,119 @@
+use crate::{Color, ColorModel};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A pixel layout corresponding to one of the frei0r [ColorModel]s.
+///
+/// Selected through [Plugin::Pixel](crate::Plugin::Pixel), which also determines the color model
+/// reported to the application, so the two can not disagree.
+///
+/// Every implementation is 4 bytes in size, has an alignment of at most 4 and is valid for any
+/// bit pattern, which makes the slice conversions below safe.
+pub trait Pixel:
+    private::Sealed + Copy + Default + PartialEq + std::fmt::Debug + Send + Sync + 'static
+{
+    /// The color model described by this layout.
+    const COLOR_MODEL: ColorModel;
+
+    /// View a frame of `u32` pixels, as passed by the application, as this layout.
+    fn from_u32_slice(pixels: &[u32]) -> &[Self] {
+        unsafe { std::slice::from_raw_parts(pixels.as_ptr().cast(), pixels.len()) }
+    }
+
+    /// View a mutable frame of `u32` pixels, as passed by the application, as this layout.
+    fn from_u32_slice_mut(pixels: &mut [u32]) -> &mut [Self] {
+        unsafe { std::slice::from_raw_parts_mut(pixels.as_mut_ptr().cast(), pixels.len()) }
+    }
+
+    /// View pixels as bytes, in memory order.
+    fn slice_as_bytes(pixels: &[Self]) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(pixels.as_ptr().cast(), size_of_val(pixels)) }
+    }
+
+    /// View pixels as mutable bytes, in memory order.
+    fn slice_as_bytes_mut(pixels: &mut [Self]) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(pixels.as_mut_ptr().cast(), size_of_val(pixels)) }
+    }
+}
+
+fn component(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// A [ColorModel::RGBA8888] pixel: red, green, blue and alpha bytes in memory order.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba8 {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Rgba8 { r, g, b, a }
+    }
+}
+
+impl private::Sealed for Rgba8 {}
+impl Pixel for Rgba8 {
+    const COLOR_MODEL: ColorModel = ColorModel::RGBA8888;
+}
+
+impl From<Color> for Rgba8 {
+    /// Convert an opaque color parameter.
+    fn from(color: Color) -> Self {
+        Rgba8::new(
+            component(color.r),
+            component(color.g),
+            component(color.b),
+            255,
+        )
+    }
+}
+
+/// A [ColorModel::BGRA8888] pixel: blue, green, red and alpha bytes in memory order.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Bgra8 {
+    pub b: u8,
+    pub g: u8,
+    pub r: u8,
+    pub a: u8,
+}
+
+impl Bgra8 {
+    pub const fn new(b: u8, g: u8, r: u8, a: u8) -> Self {
+        Bgra8 { b, g, r, a }
+    }
+}
+
+impl private::Sealed for Bgra8 {}
+impl Pixel for Bgra8 {
+    const COLOR_MODEL: ColorModel = ColorModel::BGRA8888;
+}
+
+impl From<Color> for Bgra8 {
+    /// Convert an opaque color parameter.
+    fn from(color: Color) -> Self {
+        Bgra8::new(
+            component(color.b),
+            component(color.g),
+            component(color.r),
+            255,
+        )
+    }
+}
+
+/// A [ColorModel::PACKED32] pixel: 32 bits in an unspecified color format.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Packed32(pub u32);
+
+impl private::Sealed for Packed32 {}
+impl Pixel for Packed32 {
+    const COLOR_MODEL: ColorModel = ColorModel::PACKED32;
+}
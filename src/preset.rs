@@ -0,0 +1,25 @@
+use crate::{Color, Position};
+use std::ffi::CStr;
+
+/// A named set of parameter values, see [Plugin::PRESETS](crate::Plugin::PRESETS).
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    /// The name the application applies the preset with.
+    pub name: &'static CStr,
+    /// Values by the parameter names reported to the application, e.g. `c"rect.x"` for a field
+    /// of a composite parameter. Parameters not listed keep their value.
+    pub values: &'static [(&'static CStr, PresetValue)],
+}
+
+/// A parameter value of a [Preset], as the application would set it.
+///
+/// Values are in the frei0r encoding of the parameter: doubles with a range and integers are
+/// normalized to [0, 1], choices are names or normalized doubles depending on their encoding.
+#[derive(Debug, Clone, Copy)]
+pub enum PresetValue {
+    Bool(bool),
+    Double(f64),
+    Color(Color),
+    Position(Position),
+    String(&'static CStr),
+}
@@ -0,0 +1,216 @@
+use crate::BoxError;
+use crate::diagnostic::{self, Diagnostic};
+use std::{
+    any::{Any, TypeId},
+    error::Error,
+    ffi::{CStr, CString},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, Weak},
+    time::SystemTime,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Data loaded from a file named by a path parameter, e.g. a LUT, an image or a font.
+///
+/// See [ParamInfo::new_path](crate::ParamInfo::new_path).
+pub trait Resource: 'static + Sized + Send + Sync {
+    /// Load the resource from `path`.
+    ///
+    /// Called during the first update after the path changed, or was set again after a failed
+    /// load or a modification of the file, never from the application's parameter calls.
+    fn load(path: &Path) -> Result<Self, BoxError>;
+}
+
+/// The value of a path parameter: the path set by the application and the [Resource] loaded
+/// from it.
+///
+/// Resources are shared by all instances of the library that load the same unmodified file.
+pub struct ResourcePath<R> {
+    path: CString,
+    pending: bool,
+    resource: Option<Arc<R>>,
+    // Modification time of the file the resource was loaded from.
+    modified: Option<SystemTime>,
+    error: Option<BoxError>,
+}
+
+impl<R: Resource> ResourcePath<R> {
+    /// A path parameter loading `path` before the first update, an empty path loads nothing.
+    pub fn new(path: &CStr) -> Self {
+        ResourcePath {
+            path: path.to_owned(),
+            pending: !path.is_empty(),
+            resource: None,
+            modified: None,
+            error: None,
+        }
+    }
+
+    /// The path set by the application.
+    pub fn path(&self) -> &CStr {
+        &self.path
+    }
+
+    /// The loaded resource, `None` if the path is empty or loading failed.
+    pub fn resource(&self) -> Option<&Arc<R>> {
+        self.resource.as_ref()
+    }
+
+    /// The error of the last load, if it failed.
+    pub fn error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.error.as_deref()
+    }
+}
+
+impl<R: Resource> Default for ResourcePath<R> {
+    fn default() -> Self {
+        Self::new(c"")
+    }
+}
+
+impl<R> std::fmt::Debug for ResourcePath<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourcePath")
+            .field("path", &self.path)
+            .field("loaded", &self.resource.is_some())
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// A [ResourcePath] with the resource type erased, used by path parameters.
+pub trait PathValue: private::Sealed + Send {
+    /// The path set by the application.
+    fn path(&self) -> &CStr;
+
+    #[doc(hidden)]
+    fn set_path(&mut self, path: &CStr);
+
+    #[doc(hidden)]
+    fn load_pending(&mut self);
+}
+
+impl<R> private::Sealed for ResourcePath<R> {}
+
+impl<R: Resource> PathValue for ResourcePath<R> {
+    fn path(&self) -> &CStr {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: &CStr) {
+        // Hosts may set every parameter before every frame, the same path is only loaded again
+        // if the last load failed or the file was modified since.
+        if self.path.as_c_str() != path {
+            self.path = path.to_owned();
+            self.pending = true;
+        } else if self.error.is_some()
+            || self.resource.is_some() && modified(&to_path(&self.path)) != self.modified
+        {
+            self.pending = true;
+        }
+    }
+
+    fn load_pending(&mut self) {
+        if !std::mem::take(&mut self.pending) {
+            return;
+        }
+        self.resource = None;
+        self.modified = None;
+        self.error = None;
+        if self.path.is_empty() {
+            return;
+        }
+        let path = to_path(&self.path);
+        match load_cached::<R>(&path) {
+            Ok((resource, modified)) => {
+                self.resource = Some(resource);
+                self.modified = modified;
+            }
+            Err(error) => {
+                diagnostic::report(&Diagnostic::LoadFailed {
+                    path: &path,
+                    error: error.as_ref(),
+                });
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn to_path(path: &CStr) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(std::ffi::OsStr::from_bytes(path.to_bytes()))
+}
+
+#[cfg(not(unix))]
+fn to_path(path: &CStr) -> PathBuf {
+    PathBuf::from(&*path.to_string_lossy())
+}
+
+/// A loaded resource, with the file it was loaded from.
+struct Entry {
+    path: PathBuf,
+    resource: TypeId,
+    modified: SystemTime,
+    // Dropped with the last instance using it.
+    value: Weak<dyn Any + Send + Sync>,
+}
+
+static CACHE: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+fn cache() -> MutexGuard<'static, Vec<Entry>> {
+    CACHE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// The modification time of a file, `None` if it can not be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Load `path`, reusing the resource loaded by another instance if the file was not modified
+/// since. Returns the resource with the modification time of the file it was loaded from.
+fn load_cached<R: Resource>(path: &Path) -> Result<(Arc<R>, Option<SystemTime>), BoxError> {
+    // Files without a modification time are not cached.
+    let modified = modified(path);
+    if let Some(modified) = modified {
+        let cached = cache()
+            .iter()
+            .find(|entry| {
+                entry.resource == TypeId::of::<R>()
+                    && entry.path == path
+                    && entry.modified == modified
+            })
+            .and_then(|entry| entry.value.upgrade());
+        if let Some(value) = cached {
+            let resource = value
+                .downcast::<R>()
+                .expect("resource cached under its type id");
+            return Ok((resource, Some(modified)));
+        }
+    }
+
+    // The lock is not held while loading, which may take a while.
+    let resource = Arc::new(R::load(path)?);
+    if let Some(modified) = modified {
+        let value: Arc<dyn Any + Send + Sync> = resource.clone();
+        let mut cache = cache();
+        cache.retain(|entry| {
+            entry.value.strong_count() > 0
+                && !(entry.resource == TypeId::of::<R>() && entry.path == path)
+        });
+        cache.push(Entry {
+            path: path.to_owned(),
+            resource: TypeId::of::<R>(),
+            modified,
+            value: Arc::downgrade(&value),
+        });
+    }
+    Ok((resource, modified))
+}
@@ -0,0 +1,112 @@
+/// How a double or position parameter moves towards a new value set by the application, see
+/// [ParamInfo::with_smoothing](crate::ParamInfo::with_smoothing).
+///
+/// Durations are in the units of the `time` passed to the updates, seconds by the frei0r spec.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Smoothing {
+    /// The plugin sees new values immediately.
+    #[default]
+    None,
+    /// Move in equal steps, reaching the new value after `frames` updates.
+    Linear { frames: u32 },
+    /// Cover half of the remaining distance every `half_life`.
+    Exponential { half_life: f64 },
+    /// Follow a critically damped spring settling in about `smooth_time`, without overshooting.
+    ///
+    /// The velocity is kept when the value is set again, so values animated by the application
+    /// are followed without kinks.
+    CriticallyDamped { smooth_time: f64 },
+}
+
+/// The value the plugin sees of a smoothed parameter, and the value set by the application.
+///
+/// Positions use both components, doubles only the first.
+#[derive(Debug)]
+pub(crate) struct Smoother {
+    smoothing: Smoothing,
+    target: [f64; 2],
+    value: [f64; 2],
+    velocity: [f64; 2],
+    // Value when the target last changed and updates since, for linear ramps.
+    start: [f64; 2],
+    frame: u32,
+}
+
+impl Smoother {
+    pub(crate) fn new(smoothing: Smoothing, value: [f64; 2]) -> Self {
+        Smoother {
+            smoothing,
+            target: value,
+            value,
+            velocity: [0.0; 2],
+            start: value,
+            frame: 0,
+        }
+    }
+
+    pub(crate) fn target(&self) -> [f64; 2] {
+        self.target
+    }
+
+    pub(crate) fn value(&self) -> [f64; 2] {
+        self.value
+    }
+
+    pub(crate) fn set_target(&mut self, target: [f64; 2]) {
+        if target != self.target {
+            self.target = target;
+            self.start = self.value;
+            self.frame = 0;
+        }
+    }
+
+    /// Move the value `elapsed` time towards the target, `None` jumps to it.
+    ///
+    /// Returns whether the value changed.
+    pub(crate) fn advance(&mut self, elapsed: Option<f64>) -> bool {
+        if self.value == self.target {
+            return false;
+        }
+        let previous = self.value;
+        match (self.smoothing, elapsed) {
+            (Smoothing::None, _) | (_, None) => self.value = self.target,
+            (Smoothing::Linear { frames }, Some(_)) => {
+                self.frame += 1;
+                let progress = (self.frame as f64 / frames as f64).min(1.0);
+                for i in 0..2 {
+                    self.value[i] = self.start[i] + (self.target[i] - self.start[i]) * progress;
+                }
+            }
+            (Smoothing::Exponential { half_life }, Some(elapsed)) => {
+                let factor = 1.0 - 0.5f64.powf(elapsed / half_life);
+                for i in 0..2 {
+                    self.value[i] += (self.target[i] - self.value[i]) * factor;
+                }
+            }
+            (Smoothing::CriticallyDamped { smooth_time }, Some(elapsed)) => {
+                // Closed form approximation from Game Programming Gems 4, chapter 1.10.
+                let omega = 2.0 / smooth_time;
+                let x = omega * elapsed;
+                let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+                for i in 0..2 {
+                    let offset = self.value[i] - self.target[i];
+                    let temp = (self.velocity[i] + omega * offset) * elapsed;
+                    self.velocity[i] = (self.velocity[i] - omega * temp) * decay;
+                    self.value[i] = self.target[i] + (offset + temp) * decay;
+                }
+            }
+        }
+        // The exponential policies only approach the target.
+        if (0..2).all(|i| settled(self.value[i], self.target[i])) {
+            self.value = self.target;
+        }
+        if self.value == self.target {
+            self.velocity = [0.0; 2];
+        }
+        self.value != previous
+    }
+}
+
+fn settled(value: f64, target: f64) -> bool {
+    (value - target).abs() <= 1e-9 * (1.0 + target.abs())
+}
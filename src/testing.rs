@@ -0,0 +1,71 @@
+//! In-process test host.
+//!
+//! Drives a [Plugin] through the same C API implementation the [plugin!](crate::plugin) exports
+//! forward to, without building or loading a shared library, so plugins can be exercised from
+//! ordinary `#[test]`s using the [host](crate::host) API.
+//!
+//! ```
+//! # use frei0r_rs2::*;
+//! # struct Invert;
+//! # impl Plugin for Invert {
+//! #     type Kind = KindFilter;
+//! #     type Pixel = Packed32;
+//! #     const PARAMS: &'static [ParamInfo<Self>] = &[];
+//! #     fn info() -> PluginInfo {
+//! #         plugin_info!(name: c"invert")
+//! #     }
+//! #     fn new(_width: usize, _height: usize) -> Self {
+//! #         Invert
+//! #     }
+//! # }
+//! # impl FilterPlugin for Invert {
+//! #     fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+//! #         for (o, i) in outframe.iter_mut().zip(inframe) {
+//! #             *o = !i;
+//! #         }
+//! #     }
+//! # }
+//! let plugin = frei0r_rs2::testing::load::<Invert>().unwrap();
+//! let mut instance = plugin.construct(8, 8).unwrap();
+//! let inframe = frei0r_rs2::host::FrameBuffer::new(64);
+//! let mut outframe = frei0r_rs2::host::FrameBuffer::new(64);
+//! instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+//! assert!(outframe.iter().all(|&pixel| pixel == u32::MAX));
+//! ```
+
+use crate::{
+    InstanceHolder, Plugin, PluginGlobal,
+    ffi::PluginKindUpdate,
+    host::{Api, HostError, LoadedPlugin},
+};
+
+/// Initialize plugin `P` in process and return it as a [LoadedPlugin].
+///
+/// `f0r_init` is called by this function and `f0r_deinit` when the returned plugin is dropped.
+pub fn load<P>() -> Result<LoadedPlugin, HostError>
+where
+    P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>,
+{
+    load_with_global::<P, ()>()
+}
+
+/// Like [load], for plugins exported with `plugin!(P, global = G)`.
+pub fn load_with_global<P, G>() -> Result<LoadedPlugin, HostError>
+where
+    P: Plugin + PluginKindUpdate<<P as Plugin>::Kind>,
+    G: PluginGlobal,
+{
+    let api = Api {
+        init: InstanceHolder::<P>::f0r_init::<G>,
+        deinit: InstanceHolder::<P>::f0r_deinit::<G>,
+        get_plugin_info: InstanceHolder::<P>::f0r_get_plugin_info,
+        get_param_info: InstanceHolder::<P>::f0r_get_param_info,
+        construct: InstanceHolder::<P>::f0r_construct,
+        destruct: InstanceHolder::<P>::f0r_destruct,
+        set_param_value: InstanceHolder::<P>::f0r_set_param_value,
+        get_param_value: InstanceHolder::<P>::f0r_get_param_value,
+        update: InstanceHolder::<P>::f0r_update,
+        update2: Some(InstanceHolder::<P>::f0r_update2),
+    };
+    unsafe { LoadedPlugin::from_api(api, None, true) }
+}
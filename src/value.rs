@@ -0,0 +1,34 @@
+use crate::{Color, Position, PresetValue};
+
+/// The value of a parameter as exchanged with the application, one variant per frei0r type.
+///
+/// Values are in the frei0r encoding of the parameter, like [PresetValue]. A snapshot of an
+/// instance lists them by the parameter names reported to the application, see
+/// [HostInstance::snapshot](crate::host::HostInstance::snapshot).
+///
+/// With the `serde` feature, values serialize as e.g. `{ "double": 0.5 }`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ParamValue {
+    Bool(bool),
+    Double(f64),
+    Color(Color),
+    Position(Position),
+    String(String),
+}
+
+impl From<PresetValue> for ParamValue {
+    fn from(value: PresetValue) -> Self {
+        match value {
+            PresetValue::Bool(value) => ParamValue::Bool(value),
+            PresetValue::Double(value) => ParamValue::Double(value),
+            PresetValue::Color(value) => ParamValue::Color(value),
+            PresetValue::Position(value) => ParamValue::Position(value),
+            PresetValue::String(value) => ParamValue::String(value.to_string_lossy().into_owned()),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use frei0r_rs2::*;
+
+#[derive(Debug, Clone, Copy)]
+struct Size {
+    width: f64,
+    height: f64,
+}
+
+impl Composite for Size {
+    const FIELDS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_double(
+            c"w",
+            c"",
+            |size| size.width,
+            |size, value| size.width = value,
+        ),
+        ParamInfo::new_double(
+            c"h",
+            c"",
+            |size| size.height,
+            |size, value| size.height = value,
+        ),
+    ];
+}
+
+struct ChangedPlugin {
+    gamma: f64,
+    invert: bool,
+    size: Size,
+    // Number of params_changed calls and the parameters of the last one.
+    calls: u32,
+    changed: u32,
+}
+
+impl Plugin for ChangedPlugin {
+    type Kind = KindSource;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"gamma",
+            c"Gamma",
+            |plugin| plugin.gamma,
+            |plugin, value| plugin.gamma = value,
+        )
+        .with_range(0.1, 4.0),
+        ParamInfo::new_bool(
+            c"invert",
+            c"Invert",
+            |plugin| plugin.invert,
+            |plugin, value| plugin.invert = value,
+        ),
+        ParamInfo::new_composite(
+            c"size",
+            c"Size",
+            |plugin| plugin.size,
+            |plugin, value| plugin.size = value,
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"changed")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        ChangedPlugin {
+            gamma: 1.0,
+            invert: false,
+            size: Size {
+                width: 0.0,
+                height: 0.0,
+            },
+            calls: 0,
+            changed: 0,
+        }
+    }
+
+    fn params_changed(&mut self, changed: &ParamSet) {
+        self.calls += 1;
+        self.changed = changed.iter().map(|index| 1 << index).sum();
+    }
+}
+
+impl SourcePlugin for ChangedPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe[0] = self.calls;
+        outframe[1] = self.changed;
+    }
+}
+
+fn render(instance: &mut host::HostInstance<'_>) -> (u32, u32) {
+    let mut outframe = host::FrameBuffer::new(instance.frame_length());
+    instance.update(0.0, None, &mut outframe).unwrap();
+    (outframe[0], outframe[1])
+}
+
+#[test]
+fn all_before_first_update() {
+    let plugin = testing::load::<ChangedPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(render(&mut instance), (1, 0b111));
+    assert_eq!(render(&mut instance), (1, 0b111));
+}
+
+#[test]
+fn batched_per_update() {
+    let plugin = testing::load::<ChangedPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    render(&mut instance);
+
+    instance.set_double(0, 0.2).unwrap();
+    instance.set_double(0, 0.3).unwrap();
+    instance.set_double(3, 0.5).unwrap();
+    assert_eq!(render(&mut instance), (2, 0b101));
+
+    instance.set_bool(1, true).unwrap();
+    assert_eq!(render(&mut instance), (3, 0b010));
+}
+
+#[test]
+fn ignored_values_are_not_changes() {
+    let plugin = testing::load::<ChangedPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    render(&mut instance);
+
+    instance.set_double(0, f64::NAN).unwrap();
+    assert_eq!(render(&mut instance), (1, 0b111));
+}
+
+#[test]
+fn param_set() {
+    let set = ParamSet::default();
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0);
+    assert!(!set.contains(0));
+    assert_eq!(set.iter().count(), 0);
+}
@@ -0,0 +1,110 @@
+use frei0r_rs2::{host::ParamType, *};
+use std::ffi::CStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+}
+
+impl Choice for BlendMode {
+    const CHOICES: &'static [(&'static CStr, Self)] = &[
+        (c"normal", BlendMode::Normal),
+        (c"multiply", BlendMode::Multiply),
+        (c"screen", BlendMode::Screen),
+    ];
+}
+
+struct ChoicePlugin {
+    mode: BlendMode,
+    fallback: BlendMode,
+}
+
+impl Plugin for ChoicePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_choice(
+            c"mode",
+            c"Blend mode",
+            ChoiceEncoding::String,
+            |plugin| plugin.mode,
+            |plugin, value| plugin.mode = value,
+        ),
+        ParamInfo::new_choice(
+            c"fallback",
+            c"Fallback blend mode",
+            ChoiceEncoding::Double,
+            |plugin| plugin.fallback,
+            |plugin, value| plugin.fallback = value,
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"choice")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        ChoicePlugin {
+            mode: BlendMode::Normal,
+            fallback: BlendMode::Normal,
+        }
+    }
+}
+
+impl SourcePlugin for ChoicePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn metadata() {
+    let ParamKind::Choice(choice) = ChoicePlugin::PARAMS[0].kind() else {
+        panic!("not a choice parameter");
+    };
+    assert_eq!(choice.encoding(), ChoiceEncoding::String);
+    assert_eq!(
+        choice.names().collect::<Vec<_>>(),
+        [c"normal", c"multiply", c"screen"]
+    );
+
+    let plugin = testing::load::<ChoicePlugin>().unwrap();
+    assert_eq!(plugin.params()[0].param_type, Some(ParamType::String));
+    assert_eq!(plugin.params()[1].param_type, Some(ParamType::Double));
+}
+
+#[test]
+fn string_encoding() {
+    let plugin = testing::load::<ChoicePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(instance.get_string(0).unwrap().as_deref(), Some("normal"));
+    instance.set_string(0, c"screen").unwrap();
+    assert_eq!(instance.get_string(0).unwrap().as_deref(), Some("screen"));
+    instance.set_string(0, c"overlay").unwrap();
+    assert_eq!(instance.get_string(0).unwrap().as_deref(), Some("screen"));
+}
+
+#[test]
+fn double_encoding() {
+    let plugin = testing::load::<ChoicePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    for (value, expected) in [
+        (0.0, 0.0),
+        (0.3, 0.0),
+        (0.34, 0.5),
+        (0.5, 0.5),
+        (0.7, 1.0),
+        (1.0, 1.0),
+        (2.0, 1.0),
+        (-1.0, 0.0),
+    ] {
+        instance.set_double(1, value).unwrap();
+        assert_eq!(instance.get_double(1).unwrap(), expected, "set to {value}");
+    }
+    instance.set_double(1, 0.5).unwrap();
+    instance.set_double(1, f64::NAN).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 0.5);
+}
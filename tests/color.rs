@@ -0,0 +1,74 @@
+#[path = "../examples/color.rs"]
+mod color;
+
+use color::ColorPlugin;
+use frei0r_rs2::{
+    Color,
+    host::{FrameBuffer, PluginType},
+};
+
+const WIDTH: usize = 8;
+const HEIGHT: usize = 8;
+
+#[test]
+fn info() {
+    let plugin = frei0r_rs2::testing::load::<ColorPlugin>().unwrap();
+    assert_eq!(plugin.info().name, "frei0r-rs2 color");
+    assert_eq!(plugin.info().plugin_type, PluginType::Source);
+    assert_eq!(plugin.info().num_params, 1);
+    assert_eq!(
+        plugin.info().major_version.to_string(),
+        env!("CARGO_PKG_VERSION_MAJOR")
+    );
+    assert_eq!(
+        plugin.info().minor_version.to_string(),
+        env!("CARGO_PKG_VERSION_MINOR")
+    );
+}
+
+#[test]
+fn param_round_trip() {
+    let plugin = frei0r_rs2::testing::load::<ColorPlugin>().unwrap();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    instance
+        .set_color(
+            0,
+            Color {
+                r: 0.25,
+                g: 0.5,
+                b: 0.75,
+            },
+        )
+        .unwrap();
+    let color = instance.get_color(0).unwrap();
+    assert_eq!((color.r, color.g, color.b), (0.25, 0.5, 0.75));
+}
+
+#[test]
+fn fills_frame() {
+    let plugin = frei0r_rs2::testing::load::<ColorPlugin>().unwrap();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    let mut outframe = FrameBuffer::new(WIDTH * HEIGHT);
+    instance.update(0.0, None, &mut outframe).unwrap();
+    assert!(outframe.iter().all(|&pixel| pixel == u32::MAX));
+}
+
+#[test]
+fn fills_frame_in_rgba_byte_order() {
+    let plugin = frei0r_rs2::testing::load::<ColorPlugin>().unwrap();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    instance
+        .set_color(
+            0,
+            Color {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+        )
+        .unwrap();
+    let mut outframe = FrameBuffer::new(WIDTH * HEIGHT);
+    instance.update(0.0, None, &mut outframe).unwrap();
+    let red = u32::from_ne_bytes([255, 0, 0, 255]);
+    assert!(outframe.iter().all(|&pixel| pixel == red));
+}
@@ -0,0 +1,238 @@
+use frei0r_rs2::{
+    host::{HostError, ParamType},
+    *,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Composite for Rect {
+    const FIELDS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_double(c"x", c"", |rect| rect.x, |rect, value| rect.x = value),
+        ParamInfo::new_double(c"y", c"", |rect| rect.y, |rect, value| rect.y = value),
+        ParamInfo::new_double(
+            c"w",
+            c"",
+            |rect| rect.width,
+            |rect, value| rect.width = value,
+        ),
+        ParamInfo::new_double(
+            c"h",
+            c"",
+            |rect| rect.height,
+            |rect, value| rect.height = value,
+        ),
+    ];
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ColorAlpha {
+    color: Color,
+    alpha: f64,
+}
+
+impl Composite for ColorAlpha {
+    const FIELDS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_color(
+            c"color",
+            c"Color without alpha",
+            |tint| tint.color,
+            |tint, value| tint.color = *value,
+        ),
+        ParamInfo::<Self>::new_double(
+            c"alpha",
+            c"Opacity",
+            |tint| tint.alpha,
+            |tint, value| tint.alpha = value,
+        )
+        .with_range(0.0, 255.0),
+    ];
+}
+
+struct CompositePlugin {
+    before: f64,
+    rect: Rect,
+    tint: ColorAlpha,
+    after: bool,
+}
+
+impl Plugin for CompositePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_double(
+            c"before",
+            c"Plain parameter",
+            |plugin| plugin.before,
+            |plugin, value| plugin.before = value,
+        ),
+        ParamInfo::new_composite(
+            c"rect",
+            c"Region",
+            |plugin| plugin.rect,
+            |plugin, value| plugin.rect = value,
+        ),
+        ParamInfo::new_composite(
+            c"tint",
+            c"Tint",
+            |plugin| plugin.tint,
+            |plugin, value| plugin.tint = value,
+        ),
+        ParamInfo::new_bool(
+            c"after",
+            c"Plain parameter",
+            |plugin| plugin.after,
+            |plugin, value| plugin.after = value,
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"composite")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        CompositePlugin {
+            before: 0.0,
+            rect: Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+            },
+            tint: ColorAlpha {
+                color: Color {
+                    r: 0.0,
+                    g: 0.0,
+                    b: 0.0,
+                },
+                alpha: 255.0,
+            },
+            after: false,
+        }
+    }
+}
+
+impl SourcePlugin for CompositePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+// Reports `rect.x` twice, as a parameter of its own and as a field of `rect`.
+struct ClashingPlugin(Rect);
+
+impl Plugin for ClashingPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    fn params() -> &'static [ParamInfo<Self>] {
+        vec![
+            ParamInfo::<Self>::new_double(
+                c"rect.x",
+                c"",
+                |plugin| plugin.0.x,
+                |plugin, value| plugin.0.x = value,
+            ),
+            ParamInfo::new_composite(
+                c"rect",
+                c"",
+                |plugin| plugin.0,
+                |plugin, value| plugin.0 = value,
+            ),
+        ]
+        .leak()
+    }
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"clashing")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        ClashingPlugin(Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        })
+    }
+}
+
+impl SourcePlugin for ClashingPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn expanded_param_info() {
+    assert_eq!(CompositePlugin::PARAMS[1].frei0r_count(), 4);
+    let plugin = testing::load::<CompositePlugin>().unwrap();
+    assert_eq!(plugin.info().num_params, 8);
+    let params = plugin
+        .params()
+        .iter()
+        .map(|param| {
+            (
+                param.name.as_str(),
+                param.param_type.unwrap(),
+                param.explanation.as_deref().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        params,
+        [
+            ("before", ParamType::Double, "Plain parameter"),
+            ("rect.x", ParamType::Double, "Region"),
+            ("rect.y", ParamType::Double, "Region"),
+            ("rect.w", ParamType::Double, "Region"),
+            ("rect.h", ParamType::Double, "Region"),
+            ("tint.color", ParamType::Color, "Color without alpha"),
+            ("tint.alpha", ParamType::Double, "Opacity"),
+            ("after", ParamType::Bool, "Plain parameter"),
+        ]
+    );
+}
+
+#[test]
+fn fields_reassembled() {
+    let plugin = testing::load::<CompositePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_double(0, 0.1).unwrap();
+    instance.set_double(1, 0.25).unwrap();
+    instance.set_double(4, 0.5).unwrap();
+    instance
+        .set_color(
+            5,
+            Color {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+            },
+        )
+        .unwrap();
+    instance.set_double(6, 0.5).unwrap();
+    instance.set_bool(7, true).unwrap();
+
+    assert_eq!(instance.get_double(0).unwrap(), 0.1);
+    let rect = [1, 2, 3, 4].map(|index| instance.get_double(index).unwrap());
+    assert_eq!(rect, [0.25, 0.0, 1.0, 0.5]);
+    let color = instance.get_color(5).unwrap();
+    assert_eq!((color.r, color.g, color.b), (1.0, 0.5, 0.0));
+    assert_eq!(instance.get_double(6).unwrap(), 0.5);
+    assert!(instance.get_bool(7).unwrap());
+}
+
+#[test]
+fn expanded_names_unique() {
+    assert!(matches!(
+        testing::load::<ClashingPlugin>(),
+        Err(HostError::InitFailed)
+    ));
+}
@@ -0,0 +1,58 @@
+use frei0r_rs2::*;
+
+struct MinSizePlugin;
+
+impl Plugin for MinSizePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"min size")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        MinSizePlugin
+    }
+
+    fn try_new(width: usize, height: usize) -> Result<Self, BoxError> {
+        if width < 64 || height < 64 {
+            Err("resolution must be at least 64x64".into())
+        } else {
+            Ok(Self::new(width, height))
+        }
+    }
+}
+
+impl SourcePlugin for MinSizePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn plugin_refuses_construction() {
+    let plugin = testing::load::<MinSizePlugin>().unwrap();
+    assert!(plugin.construct(32, 64).is_err());
+    assert!(plugin.construct(64, 64).is_ok());
+}
+
+#[test]
+fn invalid_dimensions_are_rejected() {
+    let plugin = testing::load::<MinSizePlugin>().unwrap();
+    for (width, height) in [
+        (0, 64),
+        (64, 0),
+        (68, 64),
+        (64, 100),
+        (4096, 64),
+        (64, 2056),
+    ] {
+        assert!(
+            plugin.construct(width, height).is_err(),
+            "{width}x{height} accepted"
+        );
+    }
+    assert!(plugin.construct(2048, 2048).is_ok());
+}
@@ -0,0 +1,120 @@
+use frei0r_rs2::{host::ParamType, *};
+use std::ffi::CString;
+
+#[derive(Frei0rParams)]
+struct DerivedPlugin {
+    #[param(explanation = "Enable the effect")]
+    enabled: bool,
+    #[param(name = "amount", explanation = "Strength of the effect")]
+    strength: f64,
+    #[param(explanation = "Tint color")]
+    tint: Color,
+    #[param(explanation = "Effect center")]
+    center: Position,
+    #[param(explanation = "Label text")]
+    label: CString,
+    #[param(explanation = "Font name")]
+    font: String,
+    #[param(explanation = "Render as outline")]
+    r#type: bool,
+    #[allow(dead_code)]
+    frames: u64,
+}
+
+impl Plugin for DerivedPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = <Self as Frei0rParams>::PARAMS;
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"derived")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        DerivedPlugin {
+            enabled: false,
+            strength: 0.5,
+            tint: Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            center: Position { x: 0.5, y: 0.5 },
+            label: CString::default(),
+            font: String::new(),
+            r#type: false,
+            frames: 0,
+        }
+    }
+}
+
+impl SourcePlugin for DerivedPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn params_in_field_order() {
+    let plugin = testing::load::<DerivedPlugin>().unwrap();
+    let params = plugin
+        .params()
+        .iter()
+        .map(|param| {
+            (
+                param.name.as_str(),
+                param.param_type,
+                param.explanation.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        params,
+        [
+            ("enabled", Some(ParamType::Bool), Some("Enable the effect")),
+            (
+                "amount",
+                Some(ParamType::Double),
+                Some("Strength of the effect")
+            ),
+            ("tint", Some(ParamType::Color), Some("Tint color")),
+            ("center", Some(ParamType::Position), Some("Effect center")),
+            ("label", Some(ParamType::String), Some("Label text")),
+            ("font", Some(ParamType::String), Some("Font name")),
+            ("type", Some(ParamType::Bool), Some("Render as outline")),
+        ]
+    );
+}
+
+#[test]
+fn params_round_trip() {
+    let plugin = testing::load::<DerivedPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_bool(0, true).unwrap();
+    instance.set_double(1, 0.75).unwrap();
+    instance
+        .set_color(
+            2,
+            Color {
+                r: 0.25,
+                g: 0.5,
+                b: 1.0,
+            },
+        )
+        .unwrap();
+    instance
+        .set_position(3, Position { x: 0.1, y: 0.9 })
+        .unwrap();
+    instance.set_string(4, c"hello").unwrap();
+    instance.set_string(5, c"sans").unwrap();
+
+    assert!(instance.get_bool(0).unwrap());
+    assert_eq!(instance.get_double(1).unwrap(), 0.75);
+    let tint = instance.get_color(2).unwrap();
+    assert_eq!((tint.r, tint.g, tint.b), (0.25, 0.5, 1.0));
+    let center = instance.get_position(3).unwrap();
+    assert_eq!((center.x, center.y), (0.1, 0.9));
+    assert_eq!(instance.get_string(4).unwrap().as_deref(), Some("hello"));
+    assert_eq!(instance.get_string(5).unwrap().as_deref(), Some("sans"));
+}
@@ -0,0 +1,29 @@
+// Kept apart from the other registry tests, setting FREI0R_PATH is not thread safe.
+mod common;
+
+use frei0r_rs2::host::PluginRegistry;
+use std::{env, env::consts::DLL_SUFFIX, fs};
+
+#[test]
+fn frei0r_path() {
+    let dir = common::temp_dir("discover");
+    let (first, second) = (dir.join("first"), dir.join("second"));
+    for (dir, example) in [(&first, "color"), (&second, "shift")] {
+        fs::create_dir_all(dir).unwrap();
+        let library = common::example_library(example);
+        fs::copy(library, dir.join(format!("{example}{DLL_SUFFIX}"))).unwrap();
+    }
+    let path = env::join_paths([&first, &second]).unwrap();
+    unsafe { env::set_var("FREI0R_PATH", path) };
+
+    // The entries replace the standard directories, in order of precedence.
+    assert_eq!(PluginRegistry::search_paths(), [first, second]);
+    let registry = unsafe { PluginRegistry::discover() };
+    let names = registry
+        .entries()
+        .iter()
+        .map(|entry| entry.info.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["frei0r-rs2 color", "frei0r-rs2 shift"]);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,118 @@
+//! A source plugin written directly against the frei0r C API, like plugins not built with this
+//! crate. Its parameters look like the output and preset parameters of this crate but are plain
+//! parameters.
+
+// The safety requirements are those of frei0r.h.
+#![allow(clippy::missing_safety_doc)]
+
+use frei0r_rs2::ffi::{
+    F0R_COLOR_MODEL_RGBA8888, F0R_PARAM_DOUBLE, F0R_PARAM_STRING, F0R_PLUGIN_TYPE_SOURCE,
+    FREI0R_MAJOR_VERSION, f0r_instance_t, f0r_param_double, f0r_param_info_t, f0r_param_string,
+    f0r_param_t, f0r_plugin_info_t,
+};
+use std::ffi::{CStr, CString, c_int, c_uint};
+
+struct Instance {
+    pixels: usize,
+    level: f64,
+    preset: CString,
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn f0r_init() -> c_int {
+    1
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn f0r_deinit() {}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_get_plugin_info(info: *mut f0r_plugin_info_t) {
+    let info = unsafe { &mut *info };
+    info.name = c"foreign".as_ptr();
+    info.author = c"none".as_ptr();
+    info.plugin_type = F0R_PLUGIN_TYPE_SOURCE as c_int;
+    info.color_model = F0R_COLOR_MODEL_RGBA8888 as c_int;
+    info.frei0r_version = FREI0R_MAJOR_VERSION as c_int;
+    info.major_version = 1;
+    info.minor_version = 0;
+    info.num_params = 2;
+    info.explanation = c"Fills the frame with a gray level".as_ptr();
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_get_param_info(info: *mut f0r_param_info_t, param_index: c_int) {
+    let info = unsafe { &mut *info };
+    match param_index {
+        0 => {
+            info.name = c"level".as_ptr();
+            info.type_ = F0R_PARAM_DOUBLE as c_int;
+            info.explanation = c"(read-only) hosts may still set it".as_ptr();
+        }
+        1 => {
+            info.name = c"preset".as_ptr();
+            info.type_ = F0R_PARAM_STRING as c_int;
+            info.explanation = c"Look: soft, hard".as_ptr();
+        }
+        _ => {}
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn f0r_construct(width: c_uint, height: c_uint) -> f0r_instance_t {
+    Box::into_raw(Box::new(Instance {
+        pixels: width as usize * height as usize,
+        level: 0.5,
+        preset: c"soft".to_owned(),
+    }))
+    .cast()
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_destruct(instance: f0r_instance_t) {
+    drop(unsafe { Box::from_raw(instance.cast::<Instance>()) });
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_set_param_value(
+    instance: f0r_instance_t,
+    param: f0r_param_t,
+    param_index: c_int,
+) {
+    let instance = unsafe { &mut *instance.cast::<Instance>() };
+    match param_index {
+        0 => instance.level = unsafe { *param.cast::<f0r_param_double>() },
+        1 => {
+            let value = unsafe { CStr::from_ptr(*param.cast::<f0r_param_string>()) };
+            instance.preset = value.to_owned();
+        }
+        _ => {}
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_get_param_value(
+    instance: f0r_instance_t,
+    param: f0r_param_t,
+    param_index: c_int,
+) {
+    let instance = unsafe { &*instance.cast::<Instance>() };
+    match param_index {
+        0 => unsafe { *param.cast::<f0r_param_double>() = instance.level },
+        1 => unsafe { *param.cast::<f0r_param_string>() = instance.preset.as_ptr().cast_mut() },
+        _ => {}
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn f0r_update(
+    instance: f0r_instance_t,
+    _time: f64,
+    _inframe: *const u32,
+    outframe: *mut u32,
+) {
+    let instance = unsafe { &*instance.cast::<Instance>() };
+    let gray = (instance.level.clamp(0.0, 1.0) * 255.0).round() as u32;
+    let pixel = 0xff00_0000 | gray << 16 | gray << 8 | gray;
+    unsafe { std::slice::from_raw_parts_mut(outframe, instance.pixels) }.fill(pixel);
+}
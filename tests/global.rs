@@ -0,0 +1,105 @@
+use frei0r_rs2::{host::HostError, *};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+static INITS: AtomicUsize = AtomicUsize::new(0);
+static DEINITS: AtomicUsize = AtomicUsize::new(0);
+
+struct Table {
+    fill: u32,
+}
+
+impl PluginGlobal for Table {
+    fn init() -> Result<Self, BoxError> {
+        INITS.fetch_add(1, Ordering::SeqCst);
+        Ok(Table { fill: 42 })
+    }
+
+    fn deinit(self) {
+        DEINITS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct TablePlugin {
+    table: Arc<Table>,
+}
+
+impl Plugin for TablePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"table")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        TablePlugin {
+            table: Table::get().expect("initialized by f0r_init"),
+        }
+    }
+}
+
+impl SourcePlugin for TablePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(self.table.fill);
+    }
+}
+
+struct Unavailable;
+
+impl PluginGlobal for Unavailable {
+    fn init() -> Result<Self, BoxError> {
+        Err("resource not found".into())
+    }
+}
+
+struct UnavailablePlugin;
+
+impl Plugin for UnavailablePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"unavailable")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        UnavailablePlugin
+    }
+}
+
+impl SourcePlugin for UnavailablePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn global_shared_by_instances() {
+    let plugin = testing::load_with_global::<TablePlugin, Table>().unwrap();
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+    for _ in 0..2 {
+        let mut instance = plugin.construct(8, 8).unwrap();
+        let mut outframe = host::FrameBuffer::new(64);
+        instance.update(0.0, None, &mut outframe).unwrap();
+        assert!(outframe.iter().all(|&pixel| pixel == 42));
+    }
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+    assert_eq!(DEINITS.load(Ordering::SeqCst), 0);
+    drop(plugin);
+    assert_eq!(DEINITS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn init_failure() {
+    assert!(matches!(
+        testing::load_with_global::<UnavailablePlugin, Unavailable>(),
+        Err(HostError::InitFailed)
+    ));
+}
@@ -0,0 +1,233 @@
+//! Calls the functions exported by `plugin!` with invalid arguments, none of which may crash.
+
+use frei0r_rs2::*;
+use std::{
+    ffi::{CStr, CString, c_int},
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static REPORTS: AtomicUsize = AtomicUsize::new(0);
+
+pub struct HostilePlugin {
+    flag: bool,
+    amount: f64,
+    color: Color,
+    position: Position,
+    text: CString,
+}
+
+impl Plugin for HostilePlugin {
+    type Kind = KindMixer2;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_bool(
+            c"flag",
+            c"A bool",
+            |plugin| plugin.flag,
+            |plugin, value| plugin.flag = value,
+        ),
+        ParamInfo::new_double(
+            c"amount",
+            c"A double",
+            |plugin| plugin.amount,
+            |plugin, value| plugin.amount = value,
+        ),
+        ParamInfo::new_color(
+            c"color",
+            c"A color",
+            |plugin| plugin.color,
+            |plugin, value| plugin.color = *value,
+        ),
+        ParamInfo::new_position(
+            c"position",
+            c"A position",
+            |plugin| plugin.position,
+            |plugin, value| plugin.position = *value,
+        ),
+        ParamInfo::new_string(
+            c"text",
+            c"A string",
+            |plugin| &plugin.text,
+            |plugin, value| plugin.text = value.to_owned(),
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"hostile")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        Self {
+            flag: false,
+            amount: 0.0,
+            color: Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            position: Position { x: 0.0, y: 0.0 },
+            text: CString::default(),
+        }
+    }
+}
+
+impl Mixer2Plugin for HostilePlugin {
+    fn update_mixer2(
+        &mut self,
+        _time: f64,
+        inframe1: &[u32],
+        inframe2: &[u32],
+        outframe: &mut [u32],
+    ) {
+        for ((out, in1), in2) in outframe.iter_mut().zip(inframe1).zip(inframe2) {
+            *out = in1 ^ in2;
+        }
+    }
+}
+
+plugin!(HostilePlugin);
+
+const INVALID_INDICES: [c_int; 4] = [-1, 5, c_int::MAX, c_int::MIN];
+
+fn count_reports(_diagnostic: &Diagnostic<'_>) {
+    REPORTS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn reports() -> usize {
+    REPORTS.load(Ordering::SeqCst)
+}
+
+fn construct() -> ffi::f0r_instance_t {
+    set_diagnostic_hook(count_reports);
+    let instance = f0r_construct(8, 8);
+    assert!(!instance.is_null());
+    instance
+}
+
+#[test]
+fn get_param_info() {
+    set_diagnostic_hook(count_reports);
+    let before = reports();
+    let mut info = ffi::f0r_param_info_t {
+        name: ptr::null(),
+        type_: -1,
+        explanation: ptr::null(),
+    };
+    for index in INVALID_INDICES {
+        unsafe { f0r_get_param_info(&mut info, index) };
+        assert!(info.name.is_null());
+    }
+    for index in 0..5 {
+        unsafe { f0r_get_param_info(ptr::null_mut(), index) };
+    }
+    unsafe { f0r_get_plugin_info(ptr::null_mut()) };
+    assert!(reports() >= before + INVALID_INDICES.len() + 6);
+
+    unsafe { f0r_get_param_info(&mut info, 4) };
+    assert_eq!(unsafe { CStr::from_ptr(info.name) }, c"text");
+}
+
+#[test]
+fn set_and_get_param_value() {
+    let instance = construct();
+    let before = reports();
+    let mut value = 1.0f64;
+    for index in INVALID_INDICES {
+        unsafe {
+            f0r_set_param_value(instance, &mut value as *mut f64 as ffi::f0r_param_t, index);
+            f0r_get_param_value(instance, &mut value as *mut f64 as ffi::f0r_param_t, index);
+        }
+        assert_eq!(value, 1.0);
+    }
+    for index in 0..5 {
+        unsafe {
+            f0r_set_param_value(instance, ptr::null_mut(), index);
+            f0r_get_param_value(instance, ptr::null_mut(), index);
+            f0r_set_param_value(
+                ptr::null_mut(),
+                &mut value as *mut f64 as ffi::f0r_param_t,
+                index,
+            );
+            f0r_get_param_value(
+                ptr::null_mut(),
+                &mut value as *mut f64 as ffi::f0r_param_t,
+                index,
+            );
+        }
+    }
+    let mut string: ffi::f0r_param_string = ptr::null_mut();
+    unsafe {
+        f0r_set_param_value(
+            instance,
+            &mut string as *mut ffi::f0r_param_string as ffi::f0r_param_t,
+            4,
+        )
+    };
+    assert!(reports() > before + 2 * INVALID_INDICES.len() + 4 * 5);
+
+    // The instance is still usable.
+    unsafe {
+        f0r_set_param_value(instance, &mut value as *mut f64 as ffi::f0r_param_t, 1);
+        value = 0.0;
+        f0r_get_param_value(instance, &mut value as *mut f64 as ffi::f0r_param_t, 1);
+    }
+    assert_eq!(value, 1.0);
+    unsafe { f0r_destruct(instance) };
+}
+
+#[test]
+fn update() {
+    let instance = construct();
+    let before = reports();
+    let inframe = [1u32; 64];
+    let mut outframe = [7u32; 64];
+    unsafe {
+        f0r_update2(
+            instance,
+            0.0,
+            inframe.as_ptr(),
+            ptr::null(),
+            ptr::null(),
+            outframe.as_mut_ptr(),
+        );
+    }
+    assert!(outframe.iter().all(|&pixel| pixel == 0));
+    unsafe {
+        f0r_update2(
+            instance,
+            0.0,
+            inframe.as_ptr(),
+            inframe.as_ptr(),
+            ptr::null(),
+            ptr::null_mut(),
+        );
+        f0r_update2(
+            ptr::null_mut(),
+            0.0,
+            inframe.as_ptr(),
+            inframe.as_ptr(),
+            ptr::null(),
+            outframe.as_mut_ptr(),
+        );
+        f0r_update(instance, 0.0, inframe.as_ptr(), outframe.as_mut_ptr());
+        f0r_destruct(ptr::null_mut());
+    }
+    assert!(reports() >= before + 5);
+
+    // Mixer2 does not need the third frame.
+    let inframe2 = [3u32; 64];
+    unsafe {
+        f0r_update2(
+            instance,
+            0.0,
+            inframe.as_ptr(),
+            inframe2.as_ptr(),
+            ptr::null(),
+            outframe.as_mut_ptr(),
+        );
+        f0r_destruct(instance);
+    }
+    assert!(outframe.iter().all(|&pixel| pixel == 2));
+}
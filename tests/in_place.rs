@@ -0,0 +1,70 @@
+//! The host may pass overlapping input and output frames.
+
+use frei0r_rs2::*;
+
+pub struct RotatePlugin;
+
+impl Plugin for RotatePlugin {
+    type Kind = KindFilter;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"rotate")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        RotatePlugin
+    }
+}
+
+impl FilterPlugin for RotatePlugin {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        // Reads pixels after the one being written, which breaks if the frames alias.
+        for (index, pixel) in outframe.iter_mut().enumerate() {
+            *pixel = inframe[(index + 1) % inframe.len()];
+        }
+    }
+}
+
+plugin!(RotatePlugin);
+
+const LENGTH: usize = 64;
+
+fn expected(frame: &[u32]) -> Vec<u32> {
+    (0..LENGTH)
+        .map(|index| frame[(index + 1) % LENGTH])
+        .collect()
+}
+
+#[test]
+fn same_buffer() {
+    let instance = f0r_construct(8, 8);
+    let mut frame = (0..LENGTH as u32).collect::<Vec<_>>();
+    let expected = expected(&frame);
+    unsafe {
+        f0r_update(instance, 0.0, frame.as_ptr(), frame.as_mut_ptr());
+        f0r_destruct(instance);
+    }
+    assert_eq!(frame, expected);
+}
+
+#[test]
+fn partial_overlap() {
+    let instance = f0r_construct(8, 8);
+    let mut buffer = (0..(LENGTH + 8) as u32).collect::<Vec<_>>();
+    let expected = expected(&buffer[8..]);
+    unsafe {
+        f0r_update2(
+            instance,
+            0.0,
+            buffer.as_ptr().add(8),
+            std::ptr::null(),
+            std::ptr::null(),
+            buffer.as_mut_ptr(),
+        );
+        f0r_destruct(instance);
+    }
+    assert_eq!(buffer[..LENGTH], expected);
+}
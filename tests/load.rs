@@ -0,0 +1,85 @@
+mod common;
+
+use frei0r_rs2::{
+    ParamValue,
+    host::{FrameBuffer, HostError, LoadedPlugin, ParamType, PluginType},
+};
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 8;
+
+fn load_shift() -> LoadedPlugin {
+    unsafe { LoadedPlugin::load(common::example_library("shift")) }.unwrap()
+}
+
+#[test]
+fn load() {
+    let plugin = load_shift();
+    assert!(plugin.built_with_crate());
+    assert_eq!(plugin.info().name, "frei0r-rs2 shift");
+    assert_eq!(plugin.info().plugin_type, PluginType::Filter);
+    let names = plugin
+        .params()
+        .iter()
+        .map(|param| (param.name.as_str(), param.param_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("xshift", Some(ParamType::Double)),
+            ("yshift", Some(ParamType::Double))
+        ]
+    );
+}
+
+#[test]
+fn update() {
+    let plugin = load_shift();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    instance.set_double(0, 0.5).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.5);
+
+    let inframe = (0..(WIDTH * HEIGHT) as u32).collect::<FrameBuffer>();
+    let mut outframe = FrameBuffer::new(WIDTH * HEIGHT);
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(outframe[0], inframe[WIDTH / 2]);
+}
+
+#[test]
+fn misaligned_frame() {
+    let plugin = load_shift();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    let inframe = FrameBuffer::new(WIDTH * HEIGHT);
+    // One pixel past a 16 byte boundary.
+    let mut buffer = vec![0u32; WIDTH * HEIGHT + 4];
+    let offset = buffer.as_ptr().align_offset(16) + 1;
+    let outframe = &mut buffer[offset..offset + WIDTH * HEIGHT];
+    assert!(matches!(
+        instance.update(0.0, Some(&inframe), outframe),
+        Err(HostError::MisalignedFrame)
+    ));
+}
+
+#[test]
+fn foreign_conventions() {
+    let plugin = unsafe { LoadedPlugin::load(common::example_library("foreign")) }.unwrap();
+    assert!(!plugin.built_with_crate());
+    // Neither the explanation of level nor the preset parameter follow this crate.
+    assert!(plugin.presets().is_empty());
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert!(matches!(
+        instance.apply_preset(c"hard"),
+        Err(HostError::UnknownParam(_))
+    ));
+    let snapshot = instance.snapshot().unwrap();
+    assert_eq!(
+        snapshot,
+        [
+            ("level".to_owned(), ParamValue::Double(0.5)),
+            ("preset".to_owned(), ParamValue::String("soft".to_owned())),
+        ]
+    );
+    instance.set_double(0, 1.0).unwrap();
+    instance.restore(&snapshot).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.5);
+}
@@ -0,0 +1,144 @@
+use frei0r_rs2::{host::HostError, *};
+
+struct MeasurePlugin {
+    gain: f64,
+    average: f64,
+    nonzero: bool,
+}
+
+impl Plugin for MeasurePlugin {
+    type Kind = KindFilter;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_double(
+            c"gain",
+            c"Gain",
+            |plugin| plugin.gain,
+            |plugin, value| plugin.gain = value,
+        ),
+        ParamInfo::<Self>::new_double(
+            c"average",
+            c"Average pixel value",
+            |plugin| plugin.average,
+            |_, _| unreachable!("output parameters are never set"),
+        )
+        .with_range(0.0, 100.0)
+        .as_output(),
+        ParamInfo::<Self>::new_bool(
+            c"nonzero",
+            c"",
+            |plugin| plugin.nonzero,
+            |_, _| unreachable!("output parameters are never set"),
+        )
+        .as_output(),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"measure")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        MeasurePlugin {
+            gain: 1.0,
+            average: 0.0,
+            nonzero: false,
+        }
+    }
+}
+
+impl FilterPlugin for MeasurePlugin {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+        let sum = inframe.iter().map(|&pixel| pixel as f64).sum::<f64>();
+        self.average = sum / inframe.len() as f64 * self.gain;
+        self.nonzero = sum > 0.0;
+    }
+}
+
+#[test]
+fn marked_read_only() {
+    let plugin = testing::load::<MeasurePlugin>().unwrap();
+    let explanations = plugin
+        .params()
+        .iter()
+        .map(|param| param.explanation.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        explanations,
+        [
+            Some("Gain"),
+            Some("(read-only) Average pixel value"),
+            Some("(read-only)")
+        ]
+    );
+    assert!(!MeasurePlugin::PARAMS[0].is_output());
+    assert!(MeasurePlugin::PARAMS[1].is_output());
+}
+
+#[test]
+fn reports_measurement() {
+    let plugin = testing::load::<MeasurePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    let inframe = (0..64)
+        .map(|index| index % 2 * 50)
+        .collect::<host::FrameBuffer>();
+    let mut outframe = host::FrameBuffer::new(64);
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 0.25);
+    assert!(instance.get_bool(2).unwrap());
+
+    instance.set_double(0, 2.0).unwrap();
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 0.5);
+}
+
+#[test]
+fn set_is_ignored() {
+    let plugin = testing::load::<MeasurePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_double(1, 0.75).unwrap();
+    instance.set_bool(2, true).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 0.0);
+    assert!(!instance.get_bool(2).unwrap());
+}
+
+/// An ordinary parameter whose explanation would read as an output parameter.
+struct MarkedPlugin;
+
+impl Plugin for MarkedPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    fn params() -> &'static [ParamInfo<Self>] {
+        vec![ParamInfo::new_bool(
+            c"locked",
+            c"(read-only) in the editor",
+            |_| false,
+            |_, _| {},
+        )]
+        .leak()
+    }
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"marked")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        MarkedPlugin
+    }
+}
+
+impl SourcePlugin for MarkedPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn read_only_explanation_rejected() {
+    assert!(matches!(
+        testing::load::<MarkedPlugin>(),
+        Err(HostError::InitFailed)
+    ));
+}
@@ -0,0 +1,88 @@
+use frei0r_rs2::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PANICS: AtomicUsize = AtomicUsize::new(0);
+
+struct PanicPlugin {
+    value: f64,
+}
+
+impl Plugin for PanicPlugin {
+    type Kind = KindFilter;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[ParamInfo::new_double(
+        c"value",
+        c"Panics when set above 0.5",
+        |plugin| plugin.value,
+        |plugin, value| {
+            if value > 0.5 {
+                panic!("value too large");
+            }
+            plugin.value = value;
+        },
+    )];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"panic")
+    }
+
+    fn new(width: usize, _height: usize) -> Self {
+        if width == 16 {
+            panic!("unsupported width");
+        }
+        Self { value: 0.0 }
+    }
+}
+
+impl FilterPlugin for PanicPlugin {
+    fn update_filter(&mut self, time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+        if time > 1.0 {
+            panic!("time too large");
+        }
+    }
+}
+
+fn count_panics(diagnostic: &Diagnostic<'_>) {
+    if let Diagnostic::Panic { .. } = diagnostic {
+        PANICS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn panics_are_contained() {
+    set_diagnostic_hook(count_panics);
+    let plugin = testing::load::<PanicPlugin>().unwrap();
+
+    assert!(plugin.construct(16, 8).is_err());
+    assert_eq!(PANICS.load(Ordering::SeqCst), 1);
+
+    let inframe = host::FrameBuffer::from_slice(&[7; 64]);
+    let mut outframe = host::FrameBuffer::new(64);
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(outframe, inframe);
+
+    // A panic during update clears the output and poisons the instance.
+    instance.update(2.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 2);
+    assert!(outframe.iter().all(|&pixel| pixel == 0));
+    outframe.fill(1);
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert!(outframe.iter().all(|&pixel| pixel == 0));
+    instance.set_double(0, 0.25).unwrap();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 2);
+
+    // Other instances are unaffected.
+    let mut other = plugin.construct(8, 8).unwrap();
+    outframe.fill(0);
+    other.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert_eq!(outframe, inframe);
+
+    // A panic while setting a parameter poisons the instance as well.
+    other.set_double(0, 0.75).unwrap();
+    assert_eq!(PANICS.load(Ordering::SeqCst), 3);
+    other.update(0.0, Some(&inframe), &mut outframe).unwrap();
+    assert!(outframe.iter().all(|&pixel| pixel == 0));
+}
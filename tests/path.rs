@@ -0,0 +1,189 @@
+use frei0r_rs2::{host::ParamType, *};
+use std::{
+    ffi::CString,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+// Every load, tests use distinct files so they can run in parallel.
+static LOADS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+fn load_count(path: &Path) -> usize {
+    LOADS.lock().unwrap().iter().filter(|p| *p == path).count()
+}
+
+/// The first byte of a file.
+struct Fill(u8);
+
+impl Resource for Fill {
+    fn load(path: &Path) -> Result<Self, BoxError> {
+        LOADS.lock().unwrap().push(path.to_owned());
+        let bytes = fs::read(path)?;
+        Ok(Fill(*bytes.first().ok_or("empty file")?))
+    }
+}
+
+struct PathPlugin {
+    fill: ResourcePath<Fill>,
+}
+
+impl Plugin for PathPlugin {
+    type Kind = KindSource;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[ParamInfo::new_path(
+        c"fill",
+        c"File containing the fill value",
+        |plugin| &plugin.fill,
+        |plugin| &mut plugin.fill,
+    )];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"path")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        PathPlugin {
+            fill: ResourcePath::default(),
+        }
+    }
+}
+
+impl SourceFramePlugin for PathPlugin {
+    fn update_source_frame(&mut self, _time: f64, mut outframe: FrameMut<'_, Packed32>) {
+        let pixel = match (self.fill.resource(), self.fill.error()) {
+            (Some(fill), _) => fill.0 as u32,
+            (None, Some(_)) => u32::MAX,
+            (None, None) => 0,
+        };
+        outframe.fill(Packed32(pixel));
+    }
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("frei0r-rs2-{}-{name}", std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn c_path(path: &Path) -> CString {
+    CString::new(path.to_str().unwrap()).unwrap()
+}
+
+fn render(instance: &mut host::HostInstance<'_>) -> u32 {
+    let mut outframe = host::FrameBuffer::new(instance.frame_length());
+    instance.update(0.0, None, &mut outframe).unwrap();
+    outframe[0]
+}
+
+#[test]
+fn loads_on_update() {
+    let path = temp_file("loads_on_update", &[7]);
+    let plugin = testing::load::<PathPlugin>().unwrap();
+    assert_eq!(plugin.params()[0].param_type, Some(ParamType::String));
+
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(render(&mut instance), 0);
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(load_count(&path), 0);
+    assert_eq!(instance.get_string(0).unwrap().as_deref(), path.to_str());
+
+    assert_eq!(render(&mut instance), 7);
+    assert_eq!(load_count(&path), 1);
+    // Setting the same path again, as hosts do before every frame, does not reload.
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 7);
+    assert_eq!(load_count(&path), 1);
+
+    instance.set_string(0, c"").unwrap();
+    assert_eq!(render(&mut instance), 0);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn shared_between_instances() {
+    let path = temp_file("shared_between_instances", &[3]);
+    let plugin = testing::load::<PathPlugin>().unwrap();
+    let mut first = plugin.construct(8, 8).unwrap();
+    let mut second = plugin.construct(8, 8).unwrap();
+    first.set_string(0, &c_path(&path)).unwrap();
+    second.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut first), 3);
+    assert_eq!(render(&mut second), 3);
+    assert_eq!(load_count(&path), 1);
+
+    // A modified file is loaded again.
+    fs::write(&path, [5]).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    let mut third = plugin.construct(8, 8).unwrap();
+    third.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut third), 5);
+    assert_eq!(load_count(&path), 2);
+    assert_eq!(render(&mut first), 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn load_error() {
+    let path = temp_file("load_error", &[]);
+    let plugin = testing::load::<PathPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), u32::MAX);
+
+    fs::write(&path, [9]).unwrap();
+    instance.set_string(0, c"").unwrap();
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 9);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn same_path_after_error() {
+    let path = temp_file("same_path_after_error", &[]);
+    let plugin = testing::load::<PathPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), u32::MAX);
+    assert_eq!(load_count(&path), 1);
+
+    // The failed load is retried when the same path is set again.
+    fs::write(&path, [4]).unwrap();
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 4);
+    assert_eq!(load_count(&path), 2);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn same_path_after_modification() {
+    let path = temp_file("same_path_after_modification", &[6]);
+    let plugin = testing::load::<PathPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 6);
+
+    // A modified file is loaded again when the same path is set again.
+    fs::write(&path, [8]).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(render(&mut instance), 6);
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 8);
+    assert_eq!(load_count(&path), 2);
+    instance.set_string(0, &c_path(&path)).unwrap();
+    assert_eq!(render(&mut instance), 8);
+    assert_eq!(load_count(&path), 2);
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,230 @@
+use frei0r_rs2::{
+    host::{HostError, ParamType},
+    *,
+};
+use std::{
+    ffi::CStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static UNKNOWN_PRESETS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Film {
+    None,
+    Kodachrome,
+}
+
+impl Choice for Film {
+    const CHOICES: &'static [(&'static CStr, Self)] =
+        &[(c"none", Film::None), (c"kodachrome", Film::Kodachrome)];
+}
+
+struct GradePlugin {
+    temperature: f64,
+    tint: Color,
+    vignette: bool,
+    film: Film,
+}
+
+impl Plugin for GradePlugin {
+    type Kind = KindFilter;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"temperature",
+            c"Color temperature",
+            |plugin| plugin.temperature,
+            |plugin, value| plugin.temperature = value,
+        )
+        .with_range(2000.0, 10000.0),
+        ParamInfo::new_color(
+            c"tint",
+            c"Tint",
+            |plugin| plugin.tint,
+            |plugin, value| plugin.tint = *value,
+        ),
+        ParamInfo::new_bool(
+            c"vignette",
+            c"Vignette",
+            |plugin| plugin.vignette,
+            |plugin, value| plugin.vignette = value,
+        ),
+        ParamInfo::new_choice(
+            c"film",
+            c"Film stock",
+            ChoiceEncoding::String,
+            |plugin| plugin.film,
+            |plugin, value| plugin.film = value,
+        ),
+    ];
+
+    const PRESETS: &'static [Preset] = &[
+        Preset {
+            name: c"warm",
+            values: &[
+                (c"temperature", PresetValue::Double(0.25)),
+                (
+                    c"tint",
+                    PresetValue::Color(Color {
+                        r: 1.0,
+                        g: 0.5,
+                        b: 0.0,
+                    }),
+                ),
+            ],
+        },
+        Preset {
+            name: c"cold",
+            values: &[(c"temperature", PresetValue::Double(0.75))],
+        },
+        Preset {
+            name: c"vintage",
+            values: &[
+                (c"vignette", PresetValue::Bool(true)),
+                (c"film", PresetValue::String(c"kodachrome")),
+                // Skipped, the rest of the preset still applies.
+                (c"grain", PresetValue::Double(0.5)),
+                (c"temperature", PresetValue::Bool(true)),
+            ],
+        },
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"grade")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        GradePlugin {
+            temperature: 6000.0,
+            tint: Color {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+            vignette: false,
+            film: Film::None,
+        }
+    }
+}
+
+impl FilterPlugin for GradePlugin {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+    }
+}
+
+#[test]
+fn preset_param() {
+    let plugin = testing::load::<GradePlugin>().unwrap();
+    assert_eq!(plugin.info().num_params, 5);
+    let preset = &plugin.params()[4];
+    assert_eq!(preset.name, "preset");
+    assert_eq!(preset.param_type, Some(ParamType::String));
+    assert_eq!(
+        preset.explanation.as_deref(),
+        Some("Apply a preset, one of: warm, cold, vintage")
+    );
+    assert_eq!(plugin.presets(), ["warm", "cold", "vintage"]);
+}
+
+#[test]
+fn applies_values() {
+    let plugin = testing::load::<GradePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(instance.get_string(4).unwrap().as_deref(), Some(""));
+
+    instance.apply_preset(c"warm").unwrap();
+    assert_eq!(instance.get_string(4).unwrap().as_deref(), Some("warm"));
+    assert_eq!(instance.get_double(0).unwrap(), 0.25);
+    let tint = instance.get_color(1).unwrap();
+    assert_eq!((tint.r, tint.g, tint.b), (1.0, 0.5, 0.0));
+
+    instance.set_string(4, c"cold").unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.75);
+    // Values not in the preset are kept.
+    assert_eq!(instance.get_color(1).unwrap().g, 0.5);
+}
+
+#[test]
+fn invalid_values_are_skipped() {
+    let plugin = testing::load::<GradePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.apply_preset(c"vintage").unwrap();
+    assert!(instance.get_bool(2).unwrap());
+    assert_eq!(
+        instance.get_string(3).unwrap().as_deref(),
+        Some("kodachrome")
+    );
+    assert_eq!(instance.get_double(0).unwrap(), 0.5);
+}
+
+fn count_unknown_presets(diagnostic: &Diagnostic<'_>) {
+    if let Diagnostic::UnknownPreset { value } = diagnostic
+        && *value == c"sepia"
+    {
+        UNKNOWN_PRESETS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn unknown_preset_is_ignored() {
+    set_diagnostic_hook(count_unknown_presets);
+    let plugin = testing::load::<GradePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.apply_preset(c"cold").unwrap();
+    instance.apply_preset(c"sepia").unwrap();
+    assert_eq!(UNKNOWN_PRESETS.load(Ordering::SeqCst), 1);
+    assert_eq!(instance.get_string(4).unwrap().as_deref(), Some("cold"));
+    assert_eq!(instance.get_double(0).unwrap(), 0.75);
+}
+
+/// A plugin without presets, its parameter named preset is an ordinary parameter.
+struct LookPlugin {
+    preset: String,
+}
+
+impl Plugin for LookPlugin {
+    type Kind = KindFilter;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[ParamInfo::new_string_owned(
+        c"preset",
+        c"Name of the look",
+        |plugin| &plugin.preset,
+        |plugin, value| plugin.preset = value,
+    )];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"look")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        LookPlugin {
+            preset: "soft".to_owned(),
+        }
+    }
+}
+
+impl FilterPlugin for LookPlugin {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+    }
+}
+
+#[test]
+fn ordinary_param_named_preset() {
+    let plugin = testing::load::<LookPlugin>().unwrap();
+    assert!(plugin.presets().is_empty());
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert!(matches!(
+        instance.apply_preset(c"hard"),
+        Err(HostError::UnknownParam(name)) if name == "preset"
+    ));
+    assert_eq!(instance.get_string(0).unwrap().as_deref(), Some("soft"));
+    assert_eq!(
+        instance.snapshot().unwrap(),
+        [("preset".to_owned(), ParamValue::String("soft".to_owned()))]
+    );
+}
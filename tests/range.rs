@@ -0,0 +1,184 @@
+use frei0r_rs2::*;
+
+struct RangePlugin {
+    angle: f64,
+    center: Position,
+    radius: i32,
+    rotation: f64,
+    opacity: f64,
+}
+
+impl Plugin for RangePlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"angle",
+            c"Rotation angle",
+            |plugin| plugin.angle,
+            |plugin, value| plugin.angle = value,
+        )
+        .with_range(-180.0, 180.0)
+        .with_step(1.0)
+        .with_unit(c"°")
+        .with_default(0.0),
+        ParamInfo::<Self>::new_position(
+            c"center",
+            c"Rotation center",
+            |plugin| plugin.center,
+            |plugin, value| plugin.center = *value,
+        )
+        .with_range(-1.0, 1.0)
+        .with_default_position(Position { x: 0.0, y: 0.0 }),
+        ParamInfo::new_int(
+            c"radius",
+            c"Blur radius in pixels",
+            0,
+            10,
+            |plugin| plugin.radius,
+            |plugin, value| plugin.radius = value,
+        ),
+        ParamInfo::new_angle(
+            c"rotation",
+            c"Rotation",
+            |plugin| plugin.rotation,
+            |plugin, value| plugin.rotation = value,
+        ),
+        ParamInfo::new_percent(
+            c"opacity",
+            c"Opacity",
+            |plugin| plugin.opacity,
+            |plugin, value| plugin.opacity = value,
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"range")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        RangePlugin {
+            angle: 0.0,
+            center: Position { x: 0.0, y: 0.0 },
+            radius: 2,
+            rotation: 0.0,
+            opacity: 100.0,
+        }
+    }
+}
+
+impl SourcePlugin for RangePlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn metadata() {
+    let angle = &RangePlugin::PARAMS[0];
+    assert_eq!(
+        angle.range(),
+        Some(Range {
+            min: -180.0,
+            max: 180.0
+        })
+    );
+    assert_eq!(angle.step(), Some(1.0));
+    assert_eq!(angle.unit(), Some(c"°"));
+    assert!(matches!(angle.default(), Some(ParamDefault::Double(0.0))));
+    let center = &RangePlugin::PARAMS[1];
+    assert_eq!(center.step(), None);
+    assert_eq!(center.unit(), None);
+    assert!(matches!(
+        center.default(),
+        Some(ParamDefault::Position(Position { x: 0.0, y: 0.0 }))
+    ));
+}
+
+#[test]
+fn double_mapped_into_range() {
+    let plugin = testing::load::<RangePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.5);
+    instance.set_double(0, 0.75).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.75);
+    instance.set_double(0, 2.0).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 1.0);
+    instance.set_double(0, f64::NAN).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 1.0);
+    instance.set_double(0, -1.0).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.0);
+}
+
+#[test]
+fn position_mapped_into_range() {
+    let plugin = testing::load::<RangePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance
+        .set_position(1, Position { x: 1.0, y: 0.25 })
+        .unwrap();
+    let center = instance.get_position(1).unwrap();
+    assert_eq!((center.x, center.y), (1.0, 0.25));
+    instance
+        .set_position(1, Position { x: -3.0, y: 0.5 })
+        .unwrap();
+    let center = instance.get_position(1).unwrap();
+    assert_eq!((center.x, center.y), (0.0, 0.5));
+}
+
+#[test]
+fn range_conversion() {
+    let range = Range {
+        min: -180.0,
+        max: 180.0,
+    };
+    assert_eq!(range.denormalize(0.75), 90.0);
+    assert_eq!(range.denormalize(-1.0), -180.0);
+    assert_eq!(range.normalize(90.0), 0.75);
+    assert_eq!(range.normalize(360.0), 1.0);
+}
+
+#[test]
+fn int_rounded_into_range() {
+    let plugin = testing::load::<RangePlugin>().unwrap();
+    assert_eq!(plugin.params()[2].param_type, Some(host::ParamType::Double));
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(instance.get_double(2).unwrap(), 0.2);
+    instance.set_double(2, 0.44).unwrap();
+    assert_eq!(instance.get_double(2).unwrap(), 0.4);
+    instance.set_double(2, 0.46).unwrap();
+    assert_eq!(instance.get_double(2).unwrap(), 0.5);
+    instance.set_double(2, 7.0).unwrap();
+    assert_eq!(instance.get_double(2).unwrap(), 1.0);
+}
+
+#[test]
+fn angle_and_percent_presets() {
+    let rotation = &RangePlugin::PARAMS[3];
+    assert_eq!(
+        rotation.range(),
+        Some(Range {
+            min: 0.0,
+            max: std::f64::consts::TAU
+        })
+    );
+    assert_eq!(rotation.unit(), Some(c"rad"));
+    let opacity = &RangePlugin::PARAMS[4];
+    assert_eq!(
+        opacity.range(),
+        Some(Range {
+            min: 0.0,
+            max: 100.0
+        })
+    );
+    assert_eq!(opacity.unit(), Some(c"%"));
+
+    let plugin = testing::load::<RangePlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    assert_eq!(instance.get_double(4).unwrap(), 1.0);
+    instance.set_double(4, 0.25).unwrap();
+    assert_eq!(instance.get_double(4).unwrap(), 0.25);
+    instance.set_double(3, 0.5).unwrap();
+    assert_eq!(instance.get_double(3).unwrap(), 0.5);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use frei0r_rs2::host::PluginRegistry;
+use std::{env::consts::DLL_SUFFIX, fs, path::Path};
+
+fn install(example: &str, dir: &Path, file: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::copy(
+        common::example_library(example),
+        dir.join(format!("{file}{DLL_SUFFIX}")),
+    )
+    .unwrap();
+}
+
+fn names(registry: &PluginRegistry) -> Vec<&str> {
+    registry
+        .entries()
+        .iter()
+        .map(|entry| entry.info.name.as_str())
+        .collect()
+}
+
+#[test]
+fn vendor_directories() {
+    let dir = common::temp_dir("vendor");
+    install("shift", &dir, "shift");
+    install("color", &dir.join("vendor"), "color");
+    // Only one level of vendor directories is scanned.
+    install("shift", &dir.join("vendor").join("nested"), "nested");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&dir, dir.join("vendor").join("loop")).unwrap();
+
+    let mut registry = PluginRegistry::new();
+    unsafe { registry.scan_dir(&dir) }.unwrap();
+    assert_eq!(names(&registry), ["frei0r-rs2 shift", "frei0r-rs2 color"]);
+    assert!(registry.duplicates().is_empty());
+    assert!(registry.failures().is_empty());
+    assert_eq!(
+        registry.find_file("color").unwrap().path,
+        dir.join("vendor").join(format!("color{DLL_SUFFIX}"))
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn precedence() {
+    let dir = common::temp_dir("precedence");
+    install("shift", &dir.join("first"), "shift");
+    install("shift", &dir.join("second"), "shift");
+    install("color", &dir.join("second"), "color");
+
+    let mut registry = PluginRegistry::new();
+    unsafe { registry.scan_dir(dir.join("first")) }.unwrap();
+    unsafe { registry.scan_dir(dir.join("second")) }.unwrap();
+    assert_eq!(names(&registry), ["frei0r-rs2 shift", "frei0r-rs2 color"]);
+    let entry = registry.get("frei0r-rs2 shift").unwrap();
+    assert!(entry.path.starts_with(dir.join("first")));
+
+    let [duplicate] = registry.duplicates() else {
+        panic!("expected one duplicate: {:?}", registry.duplicates());
+    };
+    assert_eq!(duplicate.name, "frei0r-rs2 shift");
+    assert_eq!(duplicate.kept, entry.path);
+    assert!(duplicate.shadowed.starts_with(dir.join("second")));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn load_failures() {
+    let dir = common::temp_dir("failures");
+    install("shift", &dir, "shift");
+    let broken = dir.join(format!("broken{DLL_SUFFIX}"));
+    fs::write(&broken, b"not a library").unwrap();
+    fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+
+    let mut registry = PluginRegistry::new();
+    unsafe { registry.scan_dir(&dir) }.unwrap();
+    assert_eq!(names(&registry), ["frei0r-rs2 shift"]);
+    let [failure] = registry.failures() else {
+        panic!("expected one failure: {:?}", registry.failures());
+    };
+    assert_eq!(failure.path, broken);
+    assert!(unsafe { registry.scan_dir(dir.join("missing")) }.is_err());
+    fs::remove_dir_all(dir).unwrap();
+}
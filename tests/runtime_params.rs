@@ -0,0 +1,126 @@
+use frei0r_rs2::{host::HostError, *};
+use std::{
+    ffi::CString,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Stands in for a file read when the plugin is loaded.
+const UNIFORMS: &str = "brightness contrast gamma";
+
+static BUILT: AtomicUsize = AtomicUsize::new(0);
+static INVALID_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+struct ShaderPlugin {
+    uniforms: [f64; 3],
+}
+
+fn get<const I: usize>(plugin: &ShaderPlugin) -> f64 {
+    plugin.uniforms[I]
+}
+
+fn set<const I: usize>(plugin: &mut ShaderPlugin, value: f64) {
+    plugin.uniforms[I] = value;
+}
+
+type Accessors = (fn(&ShaderPlugin) -> f64, fn(&mut ShaderPlugin, f64));
+
+const ACCESSORS: [Accessors; 3] = [
+    (get::<0>, set::<0>),
+    (get::<1>, set::<1>),
+    (get::<2>, set::<2>),
+];
+
+impl Plugin for ShaderPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    fn params() -> &'static [ParamInfo<Self>] {
+        BUILT.fetch_add(1, Ordering::SeqCst);
+        UNIFORMS
+            .split_whitespace()
+            .zip(ACCESSORS)
+            .map(|(name, (get, set))| {
+                let name = CString::new(name).unwrap().into_boxed_c_str();
+                ParamInfo::new_double(Box::leak(name), c"Shader uniform", get, set)
+            })
+            .collect::<Vec<_>>()
+            .leak()
+    }
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"shader")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        ShaderPlugin { uniforms: [0.0; 3] }
+    }
+}
+
+impl SourcePlugin for ShaderPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+struct InvalidPlugin;
+
+impl Plugin for InvalidPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    fn params() -> &'static [ParamInfo<Self>] {
+        INVALID_BUILT.fetch_add(1, Ordering::SeqCst);
+        let param = || ParamInfo::new_bool(c"same", c"", |_| false, |_, _| {});
+        vec![param(), param()].leak()
+    }
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"invalid")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        InvalidPlugin
+    }
+}
+
+impl SourcePlugin for InvalidPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+    }
+}
+
+#[test]
+fn built_once() {
+    let plugin = testing::load::<ShaderPlugin>().unwrap();
+    let names = plugin
+        .params()
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["brightness", "contrast", "gamma"]);
+
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.set_double(1, 0.5).unwrap();
+    instance.set_double(2, 0.25).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 0.5);
+    assert_eq!(instance.get_double(2).unwrap(), 0.25);
+    drop(instance);
+    drop(plugin);
+
+    testing::load::<ShaderPlugin>().unwrap();
+    assert_eq!(BUILT.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn invalid_list_fails_init() {
+    assert!(matches!(
+        testing::load::<InvalidPlugin>(),
+        Err(HostError::InitFailed)
+    ));
+    // The failure is kept, the list is not built and rejected again.
+    assert!(matches!(
+        testing::load::<InvalidPlugin>(),
+        Err(HostError::InitFailed)
+    ));
+    assert_eq!(INVALID_BUILT.load(Ordering::SeqCst), 1);
+}
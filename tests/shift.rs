@@ -0,0 +1,57 @@
+#[path = "../examples/shift.rs"]
+mod shift;
+
+use frei0r_rs2::host::{FrameBuffer, ParamType, PluginType};
+use shift::ShiftPlugin;
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 8;
+
+#[test]
+fn info() {
+    let plugin = frei0r_rs2::testing::load::<ShiftPlugin>().unwrap();
+    assert_eq!(plugin.info().name, "frei0r-rs2 shift");
+    assert_eq!(plugin.info().plugin_type, PluginType::Filter);
+    let names = plugin
+        .params()
+        .iter()
+        .map(|param| (param.name.as_str(), param.param_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("xshift", Some(ParamType::Double)),
+            ("yshift", Some(ParamType::Double))
+        ]
+    );
+}
+
+#[test]
+fn param_round_trip() {
+    let plugin = frei0r_rs2::testing::load::<ShiftPlugin>().unwrap();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.0);
+    instance.set_double(0, 0.25).unwrap();
+    instance.set_double(1, 0.5).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 0.25);
+    assert_eq!(instance.get_double(1).unwrap(), 0.5);
+}
+
+#[test]
+fn shifts_pixels() {
+    let plugin = frei0r_rs2::testing::load::<ShiftPlugin>().unwrap();
+    let mut instance = plugin.construct(WIDTH as u32, HEIGHT as u32).unwrap();
+    instance.set_double(0, 0.25).unwrap();
+    instance.set_double(1, 0.5).unwrap();
+
+    let inframe = (0..(WIDTH * HEIGHT) as u32).collect::<FrameBuffer>();
+    let mut outframe = FrameBuffer::new(WIDTH * HEIGHT);
+    instance.update(0.0, Some(&inframe), &mut outframe).unwrap();
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let source = ((y + HEIGHT / 2) % HEIGHT) * WIDTH + (x + WIDTH / 4) % WIDTH;
+            assert_eq!(outframe[y * WIDTH + x], inframe[source], "pixel {x},{y}");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use frei0r_rs2::*;
+
+struct SmoothPlugin {
+    linear: f64,
+    exponential: f64,
+    spring: f64,
+    position: Position,
+}
+
+impl Plugin for SmoothPlugin {
+    type Kind = KindSource;
+    type Pixel = Packed32;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"linear",
+            c"Linear",
+            |plugin| plugin.linear,
+            |plugin, value| plugin.linear = value,
+        )
+        .with_range(0.0, 100.0)
+        .with_smoothing(Smoothing::Linear { frames: 4 }),
+        ParamInfo::<Self>::new_double(
+            c"exponential",
+            c"Exponential",
+            |plugin| plugin.exponential,
+            |plugin, value| plugin.exponential = value,
+        )
+        .with_smoothing(Smoothing::Exponential { half_life: 1.0 }),
+        ParamInfo::<Self>::new_double(
+            c"spring",
+            c"Critically damped",
+            |plugin| plugin.spring,
+            |plugin, value| plugin.spring = value,
+        )
+        .with_smoothing(Smoothing::CriticallyDamped { smooth_time: 0.5 }),
+        ParamInfo::<Self>::new_position(
+            c"position",
+            c"Position",
+            |plugin| plugin.position,
+            |plugin, value| plugin.position = *value,
+        )
+        .with_smoothing(Smoothing::Linear { frames: 2 }),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"smoothing")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        SmoothPlugin {
+            linear: 0.0,
+            exponential: 0.0,
+            spring: 0.0,
+            position: Position { x: 0.0, y: 0.0 },
+        }
+    }
+}
+
+impl SourcePlugin for SmoothPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        let values = [
+            self.linear,
+            self.exponential,
+            self.spring,
+            self.position.x,
+            self.position.y,
+        ];
+        for (pixel, value) in outframe.iter_mut().zip(values) {
+            *pixel = (value * 1000.0).round() as u32;
+        }
+    }
+}
+
+/// The values seen by the plugin, in thousandths.
+fn render(instance: &mut host::HostInstance<'_>, time: f64) -> [u32; 5] {
+    let mut outframe = host::FrameBuffer::new(instance.frame_length());
+    instance.update(time, None, &mut outframe).unwrap();
+    outframe[..5].try_into().unwrap()
+}
+
+fn instance(plugin: &host::LoadedPlugin) -> host::HostInstance<'_> {
+    let mut instance = plugin.construct(8, 8).unwrap();
+    render(&mut instance, 0.0);
+    instance
+}
+
+#[test]
+fn linear_ramp() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = instance(&plugin);
+    instance.set_double(0, 1.0).unwrap();
+    assert_eq!(instance.get_double(0).unwrap(), 1.0);
+    let ramp = [0.04, 0.08, 0.12, 0.16, 0.2].map(|time| render(&mut instance, time)[0]);
+    assert_eq!(ramp, [25_000, 50_000, 75_000, 100_000, 100_000]);
+
+    // A new target starts a new ramp from the current value.
+    instance.set_double(0, 0.5).unwrap();
+    render(&mut instance, 0.24);
+    instance.set_double(0, 0.0).unwrap();
+    assert_eq!(render(&mut instance, 0.28)[0], 65_625);
+}
+
+#[test]
+fn exponential() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = instance(&plugin);
+    instance.set_double(1, 1.0).unwrap();
+    assert_eq!(render(&mut instance, 1.0)[1], 500);
+    assert_eq!(render(&mut instance, 1.0)[1], 500);
+    assert_eq!(render(&mut instance, 2.0)[1], 750);
+    assert_eq!(render(&mut instance, 100.0)[1], 1000);
+}
+
+#[test]
+fn critically_damped() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = instance(&plugin);
+    instance.set_double(2, 1.0).unwrap();
+    let mut previous = 0;
+    for frame in 1..=250 {
+        let value = render(&mut instance, frame as f64 / 25.0)[2];
+        assert!(value >= previous && value <= 1000, "frame {frame}: {value}");
+        previous = value;
+    }
+    assert_eq!(previous, 1000);
+}
+
+#[test]
+fn position() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = instance(&plugin);
+    instance
+        .set_position(3, Position { x: 1.0, y: 0.5 })
+        .unwrap();
+    assert_eq!(render(&mut instance, 0.04)[3..], [500, 250]);
+    assert_eq!(render(&mut instance, 0.08)[3..], [1000, 500]);
+    let position = instance.get_position(3).unwrap();
+    assert_eq!((position.x, position.y), (1.0, 0.5));
+}
+
+#[test]
+fn nan_ignored() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = instance(&plugin);
+    instance.set_double(1, 1.0).unwrap();
+    assert_eq!(render(&mut instance, 1.0)[1], 500);
+    // The exponential parameter has no range, the previous target is kept.
+    instance.set_double(1, f64::NAN).unwrap();
+    assert_eq!(instance.get_double(1).unwrap(), 1.0);
+    assert_eq!(render(&mut instance, 2.0)[1], 750);
+    assert_eq!(render(&mut instance, 100.0)[1], 1000);
+}
+
+#[test]
+fn jumps_when_seeking_back() {
+    let plugin = testing::load::<SmoothPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    // Values set before the first update are not smoothed.
+    instance.set_double(1, 1.0).unwrap();
+    assert_eq!(render(&mut instance, 5.0)[1], 1000);
+
+    instance.set_double(1, 0.0).unwrap();
+    assert_eq!(render(&mut instance, 6.0)[1], 500);
+    assert_eq!(render(&mut instance, 1.0)[1], 0);
+}
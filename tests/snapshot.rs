@@ -0,0 +1,260 @@
+use frei0r_rs2::*;
+use std::ffi::CStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Add,
+    Multiply,
+}
+
+impl Choice for Mode {
+    const CHOICES: &'static [(&'static CStr, Self)] =
+        &[(c"add", Mode::Add), (c"multiply", Mode::Multiply)];
+}
+
+struct LayerPlugin {
+    gain: f64,
+    invert: bool,
+    tint: Color,
+    center: Position,
+    label: String,
+    mode: Mode,
+    level: f64,
+}
+
+impl Plugin for LayerPlugin {
+    type Kind = KindFilter;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"gain",
+            c"Gain",
+            |plugin| plugin.gain,
+            |plugin, value| plugin.gain = value,
+        )
+        .with_range(0.0, 4.0),
+        ParamInfo::new_bool(
+            c"invert",
+            c"Invert",
+            |plugin| plugin.invert,
+            |plugin, value| plugin.invert = value,
+        ),
+        ParamInfo::new_color(
+            c"tint",
+            c"Tint",
+            |plugin| plugin.tint,
+            |plugin, value| plugin.tint = *value,
+        ),
+        ParamInfo::new_position(
+            c"center",
+            c"Center",
+            |plugin| plugin.center,
+            |plugin, value| plugin.center = *value,
+        ),
+        ParamInfo::new_string_owned(
+            c"label",
+            c"Label",
+            |plugin| &plugin.label,
+            |plugin, value| plugin.label = value,
+        ),
+        ParamInfo::new_choice(
+            c"mode",
+            c"Blend mode",
+            ChoiceEncoding::String,
+            |plugin| plugin.mode,
+            |plugin, value| plugin.mode = value,
+        ),
+        ParamInfo::<Self>::new_double(
+            c"level",
+            c"Output level",
+            |plugin| plugin.level,
+            |plugin, value| plugin.level = value,
+        )
+        .as_output(),
+    ];
+
+    const PRESETS: &'static [Preset] = &[Preset {
+        name: c"bright",
+        values: &[(c"gain", PresetValue::Double(1.0))],
+    }];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"layer", major_version: 1)
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        LayerPlugin {
+            gain: 1.0,
+            invert: false,
+            tint: Color {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+            center: Position { x: 0.5, y: 0.5 },
+            label: String::new(),
+            mode: Mode::Add,
+            level: 0.0,
+        }
+    }
+}
+
+impl FilterPlugin for LayerPlugin {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+    }
+}
+
+/// A later version of [LayerPlugin]: `invert` is gone, `blur` is new and `center` is a double.
+struct LayerPlugin2 {
+    gain: f64,
+    blur: bool,
+    center: f64,
+    label: String,
+}
+
+impl Plugin for LayerPlugin2 {
+    type Kind = KindFilter;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::<Self>::new_double(
+            c"gain",
+            c"Gain",
+            |plugin| plugin.gain,
+            |plugin, value| plugin.gain = value,
+        )
+        .with_range(0.0, 4.0),
+        ParamInfo::new_bool(
+            c"blur",
+            c"Blur",
+            |plugin| plugin.blur,
+            |plugin, value| plugin.blur = value,
+        ),
+        ParamInfo::new_double(
+            c"center",
+            c"Center",
+            |plugin| plugin.center,
+            |plugin, value| plugin.center = value,
+        ),
+        ParamInfo::new_string_owned(
+            c"label",
+            c"Label",
+            |plugin| &plugin.label,
+            |plugin, value| plugin.label = value,
+        ),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"layer", major_version: 2)
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        LayerPlugin2 {
+            gain: 1.0,
+            blur: true,
+            center: 0.25,
+            label: String::new(),
+        }
+    }
+}
+
+impl FilterPlugin for LayerPlugin2 {
+    fn update_filter(&mut self, _time: f64, inframe: &[u32], outframe: &mut [u32]) {
+        outframe.copy_from_slice(inframe);
+    }
+}
+
+fn edited(instance: &mut host::HostInstance<'_>) {
+    instance.set_double(0, 0.5).unwrap();
+    instance.set_bool(1, true).unwrap();
+    instance
+        .set_color(
+            2,
+            Color {
+                r: 0.0,
+                g: 0.5,
+                b: 1.0,
+            },
+        )
+        .unwrap();
+    instance
+        .set_position(3, Position { x: 0.25, y: 0.75 })
+        .unwrap();
+    instance.set_string(4, c"title").unwrap();
+    instance.set_string(5, c"multiply").unwrap();
+}
+
+fn expected() -> Vec<(String, ParamValue)> {
+    vec![
+        ("gain".to_owned(), ParamValue::Double(0.5)),
+        ("invert".to_owned(), ParamValue::Bool(true)),
+        (
+            "tint".to_owned(),
+            ParamValue::Color(Color {
+                r: 0.0,
+                g: 0.5,
+                b: 1.0,
+            }),
+        ),
+        (
+            "center".to_owned(),
+            ParamValue::Position(Position { x: 0.25, y: 0.75 }),
+        ),
+        ("label".to_owned(), ParamValue::String("title".to_owned())),
+        ("mode".to_owned(), ParamValue::String("multiply".to_owned())),
+    ]
+}
+
+#[test]
+fn snapshot() {
+    let plugin = testing::load::<LayerPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    edited(&mut instance);
+    // Output and preset parameters are not part of the state.
+    assert_eq!(instance.snapshot().unwrap(), expected());
+}
+
+#[test]
+fn round_trip() {
+    let plugin = testing::load::<LayerPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    edited(&mut instance);
+    let snapshot = instance.snapshot().unwrap();
+
+    let mut restored = plugin.construct(8, 8).unwrap();
+    restored.restore(&snapshot).unwrap();
+    assert_eq!(restored.snapshot().unwrap(), snapshot);
+}
+
+#[test]
+fn other_version() {
+    let plugin = testing::load::<LayerPlugin>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    edited(&mut instance);
+    let snapshot = instance.snapshot().unwrap();
+
+    let plugin = testing::load::<LayerPlugin2>().unwrap();
+    let mut instance = plugin.construct(8, 8).unwrap();
+    instance.restore(&snapshot).unwrap();
+    assert_eq!(
+        instance.snapshot().unwrap(),
+        [
+            ("gain".to_owned(), ParamValue::Double(0.5)),
+            ("blur".to_owned(), ParamValue::Bool(true)),
+            ("center".to_owned(), ParamValue::Double(0.25)),
+            ("label".to_owned(), ParamValue::String("title".to_owned())),
+        ]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+    let snapshot = expected();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert!(json.starts_with(r#"[["gain",{"double":0.5}],["invert",{"bool":true}]"#));
+    let parsed: Vec<(String, ParamValue)> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, snapshot);
+}
@@ -0,0 +1,122 @@
+//! String values handed to the host stay valid after the plugin changes its own string, and
+//! values that keep changing do not pile up.
+
+use frei0r_rs2::*;
+use std::ffi::{CStr, c_char};
+
+pub struct LabelPlugin {
+    label: String,
+    status: String,
+    frames: u64,
+}
+
+impl Plugin for LabelPlugin {
+    type Kind = KindSource;
+    type Pixel = Rgba8;
+
+    const PARAMS: &'static [ParamInfo<Self>] = &[
+        ParamInfo::new_string_owned(
+            c"label",
+            c"Text to draw",
+            |plugin| &plugin.label,
+            |plugin, value| plugin.label = value,
+        ),
+        ParamInfo::<Self>::new_string_owned(
+            c"status",
+            c"Frames drawn",
+            |plugin| &plugin.status,
+            |plugin, value| plugin.status = value,
+        )
+        .as_output(),
+    ];
+
+    fn info() -> PluginInfo {
+        plugin_info!(name: c"label")
+    }
+
+    fn new(_width: usize, _height: usize) -> Self {
+        LabelPlugin {
+            label: "first".to_owned(),
+            status: String::new(),
+            frames: 0,
+        }
+    }
+}
+
+impl SourcePlugin for LabelPlugin {
+    fn update_source(&mut self, _time: f64, outframe: &mut [u32]) {
+        outframe.fill(0);
+        self.frames += 1;
+        self.status = format!("{} frames", self.frames);
+    }
+}
+
+plugin!(LabelPlugin);
+
+unsafe fn get_label(instance: ffi::f0r_instance_t) -> *const c_char {
+    let mut value: ffi::f0r_param_string = std::ptr::null_mut();
+    unsafe { f0r_get_param_value(instance, (&raw mut value).cast(), 0) };
+    value
+}
+
+unsafe fn set_label(instance: ffi::f0r_instance_t, label: &CStr) {
+    let mut value = label.as_ptr() as ffi::f0r_param_string;
+    unsafe { f0r_set_param_value(instance, (&raw mut value).cast(), 0) };
+}
+
+#[test]
+fn value_outlives_set() {
+    let instance = f0r_construct(8, 8);
+    unsafe {
+        let first = get_label(instance);
+        assert_eq!(CStr::from_ptr(first), c"first");
+        assert_eq!(get_label(instance), first);
+
+        set_label(instance, c"second");
+        // The previous value stays readable until the parameter is set again.
+        assert_eq!(CStr::from_ptr(first), c"first");
+        let second = get_label(instance);
+        assert_eq!(CStr::from_ptr(second), c"second");
+        assert_eq!(CStr::from_ptr(first), c"first");
+
+        set_label(instance, c"third");
+        assert_eq!(CStr::from_ptr(second), c"second");
+        assert_eq!(CStr::from_ptr(get_label(instance)), c"third");
+        assert_eq!(CStr::from_ptr(second), c"second");
+        f0r_destruct(instance);
+    }
+}
+
+#[test]
+fn lossy_conversion() {
+    let instance = f0r_construct(8, 8);
+    unsafe {
+        set_label(instance, c"caf\xe9");
+        assert_eq!(CStr::from_ptr(get_label(instance)), c"caf\u{fffd}");
+        f0r_destruct(instance);
+    }
+}
+
+#[test]
+fn changing_value_bounded() {
+    let mut instance = ffi::Instance::<LabelPlugin>::try_new(8, 8).unwrap();
+    let mut outframe = vec![0; instance.frame_length()];
+    let read = |instance: &mut ffi::Instance<LabelPlugin>| {
+        let mut value: ffi::f0r_param_string = std::ptr::null_mut();
+        unsafe { instance.f0r_get_param_value((&raw mut value).cast(), 1) };
+        value
+    };
+    let mut previous = (read(&mut instance), String::new());
+    for frame in 1..=1000 {
+        let null = std::ptr::null();
+        unsafe { instance.f0r_update2(0.0, null, null, null, outframe.as_mut_ptr()) };
+        let current = (read(&mut instance), format!("{frame} frames"));
+        unsafe {
+            assert_eq!(CStr::from_ptr(current.0).to_str(), Ok(current.1.as_str()));
+            // The value from the previous read stays readable.
+            assert_eq!(CStr::from_ptr(previous.0).to_str(), Ok(previous.1.as_str()));
+        }
+        assert!(instance.string_buffers(1) <= 2);
+        previous = current;
+    }
+}